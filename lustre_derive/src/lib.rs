@@ -0,0 +1,247 @@
+//! `#[derive(Lustre)]` implements `lustre_lib::object::FromLustre` and
+//! `lustre_lib::object::IntoLustre` for structs and enums.
+//!
+//! * Structs with named fields become association lists, `((X . 1) (Y . 2))`.
+//! * Tuple structs become lists, `(1 2)`, and unit structs nil.
+//! * Unit variants become a symbol, `RED`, and other variants a pair of the
+//!   variant symbol and the payload as above, `(CIRCLE 1 2)` or
+//!   `(RECT (W . 1) (H . 2))`.
+//!
+//! A field of type `Option<_>` may be left out of a record and reads as
+//! `None`; any other missing field is `Error::MissingField`.
+//!
+//! Names are upper-cased with words separated by `-`, so `first_name`
+//! becomes `FIRST-NAME` and `DarkRed` becomes `DARK-RED`, then spelled as the
+//! environment's `Case` spells builtins: `first-name` under `Case::Preserve`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, Type};
+
+#[proc_macro_derive(Lustre)]
+pub fn derive_lustre(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn lisp_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_lowercase = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            result.push('-');
+            previous_lowercase = false;
+            continue;
+        }
+        if ch.is_uppercase() && previous_lowercase {
+            result.push('-');
+        }
+        previous_lowercase = ch.is_lowercase() || ch.is_numeric();
+        result.extend(ch.to_uppercase());
+    }
+    result
+}
+
+fn expand(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(syn::parse_quote!(::lustre_lib::object::FromLustre));
+            param
+                .bounds
+                .push(syn::parse_quote!(::lustre_lib::object::IntoLustre));
+        }
+    }
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let (from_body, into_body) = match &input.data {
+        Data::Struct(data) => (
+            from_fields(quote!(Self), &data.fields, quote!(obj)),
+            into_struct(&data.fields),
+        ),
+        Data::Enum(data) => {
+            let mut from_arms = Vec::new();
+            let mut into_arms = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let symbol = lisp_name(&ident.to_string());
+                let from = from_fields(quote!(Self::#ident), &variant.fields, quote!(&payload));
+                from_arms.push(quote!(if name == lustre_case.builtin(#symbol) { #from } else));
+                into_arms.push(into_variant(ident, &symbol, &variant.fields));
+            }
+            (
+                quote! {
+                    let (name, payload) = ::lustre_lib::object::variant(obj)?;
+                    #(#from_arms)* {
+                        Err(::lustre_lib::errors::Error::UnknownVariant(name.to_string()))
+                    }
+                },
+                quote! {
+                    match self {
+                        #(#into_arms)*
+                    }
+                },
+            )
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "Lustre can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::lustre_lib::object::FromLustre for #name #type_generics #where_clause {
+            fn from_lustre(
+                obj: &::lustre_lib::object::RefObject,
+            ) -> ::lustre_lib::errors::Result<Self> {
+                Self::from_lustre_with_case(obj, ::lustre_lib::symbol::Case::default())
+            }
+
+            fn from_lustre_with_case(
+                obj: &::lustre_lib::object::RefObject,
+                lustre_case: ::lustre_lib::symbol::Case,
+            ) -> ::lustre_lib::errors::Result<Self> {
+                #from_body
+            }
+        }
+
+        impl #impl_generics ::lustre_lib::object::IntoLustre for #name #type_generics #where_clause {
+            fn into_lustre(self) -> ::lustre_lib::object::ResultRefObject {
+                self.into_lustre_with_case(::lustre_lib::symbol::Case::default())
+            }
+
+            fn into_lustre_with_case(
+                self,
+                lustre_case: ::lustre_lib::symbol::Case,
+            ) -> ::lustre_lib::object::ResultRefObject {
+                #into_body
+            }
+        }
+    })
+}
+
+fn field_values(count: usize) -> Vec<Ident> {
+    (0..count)
+        .map(|i| Ident::new(&format!("value{}", i), Span::call_site()))
+        .collect()
+}
+
+/// Whether the type is written `Option<_>`, however qualified.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Builds `constructor` from `source`, an alist, a list or nil depending on the fields.
+fn from_fields(constructor: TokenStream2, fields: &Fields, source: TokenStream2) -> TokenStream2 {
+    match fields {
+        Fields::Named(fields) => {
+            let assignments = fields.named.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let key = lisp_name(&ident.to_string());
+                if is_option(&field.ty) {
+                    quote!(#ident: ::lustre_lib::object::optional_record_field(#source, #key, lustre_case)?)
+                } else {
+                    quote!(#ident: ::lustre_lib::object::record_field(#source, #key, lustre_case)?)
+                }
+            });
+            quote!(Ok(#constructor { #(#assignments),* }))
+        }
+        Fields::Unnamed(fields) => {
+            let values = field_values(fields.unnamed.len());
+            quote! {
+                match ::lustre_lib::object::list_elements(#source)?.as_slice() {
+                    [#(#values),*] => Ok(#constructor(
+                        #(::lustre_lib::object::FromLustre::from_lustre_with_case(#values, lustre_case)?),*
+                    )),
+                    _ => Err(::lustre_lib::errors::Error::WrongArgumentCount),
+                }
+            }
+        }
+        Fields::Unit => quote!(Ok(#constructor)),
+    }
+}
+
+fn into_struct(fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(fields) => {
+            let pairs = fields.named.iter().map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let key = lisp_name(&ident.to_string());
+                quote!((#key, ::lustre_lib::object::IntoLustre::into_lustre_with_case(self.#ident, lustre_case)?))
+            });
+            quote!(Ok(::lustre_lib::object::record(vec![#(#pairs),*], lustre_case)))
+        }
+        Fields::Unnamed(fields) => {
+            let indexes = (0..fields.unnamed.len()).map(syn::Index::from);
+            quote! {
+                Ok(::lustre_lib::object::list(vec![
+                    #(::lustre_lib::object::IntoLustre::into_lustre_with_case(self.#indexes, lustre_case)?),*
+                ]))
+            }
+        }
+        Fields::Unit => quote!(::lustre_lib::object::result_nil()),
+    }
+}
+
+fn into_variant(ident: &Ident, symbol: &str, fields: &Fields) -> TokenStream2 {
+    let symbol = quote!(::lustre_lib::object::Object::Symbol(lustre_case.builtin(#symbol)).into());
+    match fields {
+        Fields::Named(fields) => {
+            let idents = fields
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap())
+                .collect::<Vec<_>>();
+            let keys = idents.iter().map(|ident| lisp_name(&ident.to_string()));
+            quote! {
+                Self::#ident { #(#idents),* } => {
+                    let payload = ::lustre_lib::object::record(vec![
+                        #((#keys, ::lustre_lib::object::IntoLustre::into_lustre_with_case(#idents, lustre_case)?)),*
+                    ], lustre_case);
+                    ::lustre_lib::object::Object::Cons(#symbol, payload).into()
+                }
+            }
+        }
+        Fields::Unnamed(fields) => {
+            let values = field_values(fields.unnamed.len());
+            quote! {
+                Self::#ident(#(#values),*) => {
+                    let payload = ::lustre_lib::object::list(vec![
+                        #(::lustre_lib::object::IntoLustre::into_lustre_with_case(#values, lustre_case)?),*
+                    ]);
+                    ::lustre_lib::object::Object::Cons(#symbol, payload).into()
+                }
+            }
+        }
+        Fields::Unit => quote!(Self::#ident => Ok(#symbol),),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lisp_name_test() {
+        assert_eq!("FIRST-NAME", lisp_name("first_name"));
+        assert_eq!("DARK-RED", lisp_name("DarkRed"));
+        assert_eq!("HTTP2", lisp_name("HTTP2"));
+    }
+}
@@ -0,0 +1,126 @@
+use lustre_derive::Lustre;
+use lustre_lib::environment::RefEnvironment;
+use lustre_lib::evaluator::{eval, operators};
+use lustre_lib::errors::Error;
+use lustre_lib::object::{record, FromLustre, IntoLustre, RefObject};
+use lustre_lib::reader::{tokenizer::Tokenizer, Reader};
+use lustre_lib::symbol::Case;
+use std::io::prelude::*;
+use std::io::Cursor;
+use tokio::runtime::Runtime;
+
+#[derive(Lustre, Debug, PartialEq, Clone)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[derive(Lustre, Debug, PartialEq)]
+struct Pair(i64, String);
+
+#[derive(Lustre, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(Point, i32),
+    Rect { top_left: Point, width: i32 },
+}
+
+fn printed(obj: &RefObject) -> String {
+    format!("{}", obj.as_ref().as_ref().unwrap())
+}
+
+#[test]
+fn round_trip() {
+    let point = Point {
+        x: 1,
+        y: 2,
+        label: None,
+    };
+    let obj = point.clone().into_lustre().unwrap();
+    assert_eq!(point, Point::from_lustre(&obj).unwrap());
+
+    let pair = Pair(3, String::from("three"));
+    let obj = Pair(3, String::from("three")).into_lustre().unwrap();
+    assert_eq!("( 3 \"three\" )", printed(&obj));
+    assert_eq!(pair, Pair::from_lustre(&obj).unwrap());
+
+    for shape in [
+        Shape::Empty,
+        Shape::Circle(point.clone(), 5),
+        Shape::Rect {
+            top_left: point.clone(),
+            width: 4,
+        },
+    ] {
+        let description = format!("{:?}", shape);
+        let obj = shape.into_lustre().unwrap();
+        assert_eq!(description, format!("{:?}", Shape::from_lustre(&obj).unwrap()));
+    }
+    assert_eq!("EMPTY", printed(&Shape::Empty.into_lustre().unwrap()));
+}
+
+#[test]
+fn missing_fields() {
+    let obj = record(vec![("X", 1.into_lustre().unwrap()), ("Y", 2.into_lustre().unwrap())], Case::Upcase);
+    let point = Point::from_lustre(&obj).unwrap();
+    assert_eq!(None, point.label);
+
+    let obj = record(vec![("X", 1.into_lustre().unwrap())], Case::Upcase);
+    match Point::from_lustre(&obj) {
+        Err(Error::MissingField(name)) => assert_eq!("Y", name),
+        other => panic!("expected a missing field, got {:?}", other),
+    }
+    let obj = record(vec![("LABEL", "l".into_lustre().unwrap())], Case::Upcase);
+    assert!(matches!(Point::from_lustre(&obj), Err(Error::MissingField(_))));
+}
+
+#[test]
+fn scripts_use_records() {
+    let mut rt = Runtime::new().unwrap();
+    let environment = RefEnvironment::new();
+    operators::initialize_operators(&environment);
+    environment.register_function("make-point", |x: i32, y: i32| Point {
+        x,
+        y,
+        label: Some(String::from("made")),
+    });
+    environment.register_function("point-sum", |p: Point| p.x + p.y);
+
+    let input = "(list (cdr (assoc 'y (make-point 3 4))) (point-sum (make-point 3 4)))";
+    let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+    let ast = Reader::new(tokenizer).read().unwrap().unwrap();
+    let result = eval(&ast, environment, &mut rt).unwrap();
+    assert_eq!("( 4 7 )", printed(&result));
+}
+
+#[test]
+fn preserved_case() {
+    let obj = Shape::Rect {
+        top_left: Point {
+            x: 1,
+            y: 2,
+            label: None,
+        },
+        width: 4,
+    }
+    .into_lustre_with_case(Case::Preserve)
+    .unwrap();
+    assert_eq!("( rect ( top-left ( x . 1 ) ( y . 2 ) ( label ) ) ( width . 4 ) )", printed(&obj));
+    assert!(matches!(Shape::from_lustre(&obj), Err(Error::UnknownVariant(_))));
+
+    let mut rt = Runtime::new().unwrap();
+    let environment = RefEnvironment::with_case(Case::Preserve);
+    operators::initialize_operators(&environment);
+    environment.register_function("make-point", |x: i32, y: i32| Point { x, y, label: None });
+    environment.register_function("shape-width", |shape: Shape| match shape {
+        Shape::Rect { width, .. } => width,
+        _ => 0,
+    });
+
+    let input = "(list (cdr (assoc 'y (make-point 3 4))) (shape-width '(rect (top-left (x . 1) (y . 2)) (width . 4))))";
+    let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+    let ast = Reader::new(tokenizer).with_case(Case::Preserve).read().unwrap().unwrap();
+    let result = eval(&ast, environment, &mut rt).unwrap();
+    assert_eq!("( 4 4 )", printed(&result));
+}
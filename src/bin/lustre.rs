@@ -0,0 +1,180 @@
+//! The `lustre` REPL and script runner. It needs the `cli` feature:
+//! `cargo install --features cli` or `cargo run --features cli`.
+
+use lustre_lib::errors::{Error, Result};
+use lustre_lib::object::RefObject;
+use lustre_lib::reader::{tokenizer::Tokenizer, Reader};
+use lustre_lib::symbol::Case;
+use lustre_lib::Interpreter;
+
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+use std::io::prelude::*;
+use std::io::Cursor;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process;
+
+const PROMPT: &str = "lustre> ";
+const CONTINUATION_PROMPT: &str = "......> ";
+const HELP: &str = "\
+:load <file>  evaluate a file
+:env          list the global symbols
+:help         show this message
+:quit         leave the REPL";
+
+fn printed(obj: &RefObject, case: Case) -> String {
+    match obj.as_ref() {
+        Some(value) => format!("{}", value),
+        None => case.builtin("NIL").to_string(),
+    }
+}
+
+fn history_file() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".lustre_history"))
+}
+
+/// Reads every form in `input`, failing with `Error::Incomplete` when the
+/// last one needs more lines.
+fn read_forms(input: &str, case: Case) -> Result<Vec<RefObject>> {
+    Reader::new(Tokenizer::new(Cursor::new(input).bytes()))
+        .with_case(case)
+        .collect()
+}
+
+/// Prints the outcome of an evaluation, turning a panic in the evaluator into
+/// an error message so a bad form doesn't end the session. Returns the exit
+/// status when the code called `EXIT`.
+fn report<F>(case: Case, evaluation: F) -> Option<i32>
+where
+    F: FnOnce() -> Result<RefObject>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(evaluation)) {
+        Ok(Ok(value)) => println!("{}", printed(&value, case)),
+        Ok(Err(Error::Exit(status))) => return Some(status),
+        Ok(Err(error)) => eprintln!("Error: {}", error),
+        Err(_) => eprintln!("Error: evaluation aborted."),
+    }
+    None
+}
+
+/// Whether the line is a REPL command rather than code such as a `:keyword`.
+fn is_command(line: &str) -> bool {
+    let word = line.split_whitespace().next().unwrap_or("");
+    [":quit", ":q", ":env", ":load", ":help"].contains(&word)
+}
+
+/// Runs a `:` command, returning the exit status when the REPL should quit.
+fn command(interpreter: &mut Interpreter, line: &str) -> Option<i32> {
+    let case = interpreter.environment().case();
+    let mut words = line.splitn(2, char::is_whitespace);
+    match (words.next().unwrap_or(""), words.next().map(str::trim)) {
+        (":quit", _) | (":q", _) => return Some(0),
+        (":env", _) => {
+            let symbols = interpreter.environment().0.read().unwrap().symbols();
+            println!("{}", symbols.join(" "));
+        }
+        (":load", Some(path)) if !path.is_empty() => return report(case, || interpreter.eval_file(path)),
+        (":load", _) => eprintln!("Usage: :load <file>"),
+        (":help", _) => println!("{}", HELP),
+        _ => eprintln!("Unknown command {}, try :help", line),
+    }
+    None
+}
+
+fn repl(interpreter: &mut Interpreter) -> i32 {
+    let case = interpreter.environment().case();
+    let mut editor = Editor::<()>::new();
+    let history = history_file();
+    if let Some(history) = &history {
+        editor.load_history(history).ok();
+    }
+
+    let mut buffer = String::new();
+    let status = loop {
+        let prompt = if buffer.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                if buffer.is_empty() && is_command(&line) {
+                    editor.add_history_entry(line.trim());
+                    match command(interpreter, line.trim()) {
+                        Some(status) => break status,
+                        None => continue,
+                    }
+                }
+                buffer.push_str(&line);
+                buffer.push('\n');
+                let forms = read_forms(&buffer, case);
+                if let Err(Error::Incomplete) = forms {
+                    continue;
+                }
+                editor.add_history_entry(buffer.trim_end());
+                buffer.clear();
+                match forms {
+                    Ok(forms) => {
+                        let exit = forms
+                            .iter()
+                            .find_map(|form| report(case, || interpreter.eval(form)));
+                        if let Some(status) = exit {
+                            break status;
+                        }
+                    }
+                    Err(error) => eprintln!("Error: {}", error),
+                }
+            }
+            Err(ReadlineError::Interrupted) => buffer.clear(),
+            Err(ReadlineError::Eof) => break 0,
+            Err(error) => {
+                eprintln!("Error: {}", error);
+                break 1;
+            }
+        }
+    };
+
+    if let Some(history) = &history {
+        editor.save_history(history).ok();
+    }
+    status
+}
+
+/// Evaluates `script` top to bottom with the remaining arguments bound to
+/// `*ARGS*`. Returns the status set by `EXIT`, or 1 on an uncaught error.
+fn run_script(interpreter: &mut Interpreter, script: &str, arguments: Vec<String>) -> i32 {
+    let result = interpreter
+        .define("*ARGS*", arguments)
+        .and_then(|_| interpreter.eval_file(script));
+    match result {
+        Ok(_) => 0,
+        Err(Error::Exit(status)) => status,
+        Err(error) => {
+            eprintln!("{}: {}", script, error);
+            1
+        }
+    }
+}
+
+fn main() {
+    let mut arguments = std::env::args().skip(1).peekable();
+    let case = if arguments.peek().map(String::as_str) == Some("--preserve-case") {
+        arguments.next();
+        Case::Preserve
+    } else {
+        Case::Upcase
+    };
+    let mut interpreter = match Interpreter::with_case(case) {
+        Ok(interpreter) => interpreter,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            process::exit(1);
+        }
+    };
+    let status = match arguments.next() {
+        Some(script) => run_script(&mut interpreter, &script, arguments.collect()),
+        None => repl(&mut interpreter),
+    };
+    process::exit(status);
+}
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
-use crate::object::{nil, RefObject};
+use crate::evaluator::Context;
+use crate::object::{nil, NativeFunction, Object, RefObject, ResultRefObject};
+use crate::symbol::{Case, Symbol};
 
-type Symbols = RwLock<HashMap<String, RefObject>>;
+type Symbols = RwLock<HashMap<Symbol, RefObject>>;
 
 #[derive(Clone)]
 pub struct RefEnvironment(pub Arc<RwLock<Environment>>);
@@ -11,35 +13,83 @@ pub struct RefEnvironment(pub Arc<RwLock<Environment>>);
 pub struct Environment {
     previous: Option<RefEnvironment>,
     symbols: Symbols,
+    case: Case,
 }
 
 impl Environment {
-    fn new() -> Self {
+    fn new(case: Case) -> Self {
         let mut value = Self {
             previous: None,
             symbols: RwLock::new(HashMap::new()),
+            case,
         };
-        value.intern("nil".to_string(), nil());
+        value.intern(case.builtin("NIL"), nil());
         value
     }
 }
 
+impl Default for RefEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RefEnvironment {
     pub fn new() -> Self {
-        Self(Arc::new(RwLock::new(Environment::new())))
+        Self::with_case(Case::default())
+    }
+    /// A global environment naming symbols as `case` does, which code read
+    /// into it has to agree with.
+    pub fn with_case(case: Case) -> Self {
+        Self(Arc::new(RwLock::new(Environment::new(case))))
     }
     pub fn from(previous: &RefEnvironment) -> RefEnvironment {
-
+        let case = previous.case();
         Self (Arc::new(RwLock::new(Environment {
             previous: Some(previous.clone()),
             symbols: RwLock::new(HashMap::new()),
+            case,
         })))
     }
+
+    pub fn case(&self) -> Case {
+        self.0.read().unwrap().case
+    }
+
+    /// Binds `name` to a host closure callable from Lustre code. The closure
+    /// receives the list of evaluated arguments and the context of the call,
+    /// e.g. to look up symbols or write to its output.
+    pub fn register<F>(&self, name: &str, function: F) -> RefObject
+    where
+        F: Fn(RefObject, &Context) -> ResultRefObject + Send + Sync + 'static,
+    {
+        let value = Object::Closure(name.to_string(), Arc::new(function)).into();
+        let mut environment = self.0.write().unwrap();
+        let symbol = environment.case.symbol(name);
+        environment.intern(symbol, value)
+    }
+
+    /// Binds `name` to a typed Rust function, e.g. `fn(i64, String) -> Result<Vec<i64>>`.
+    /// Arguments are checked for count and type before the function is called.
+    pub fn register_function<Args, F>(&self, name: &str, function: F) -> RefObject
+    where
+        F: NativeFunction<Args>,
+    {
+        self.register(name, move |arguments, context| {
+            function.call(&arguments, context.environment.case())
+        })
+    }
+
+    pub fn unregister(&self, name: &str) {
+        let mut environment = self.0.write().unwrap();
+        let symbol = environment.case.symbol(name);
+        environment.unintern(symbol);
+    }
 }
 
 impl Environment {
-    pub fn find_symbol(&self, symbol: &String) -> Option<RefObject> {
-        if let Some(value) = self.symbols.read().unwrap().get(&symbol.to_uppercase()) {
+    pub fn find_symbol(&self, symbol: Symbol) -> Option<RefObject> {
+        if let Some(value) = self.symbols.read().unwrap().get(&symbol) {
             Some(Arc::clone(value))
         } else {
             if let Some(previous) = &self.previous {
@@ -50,14 +100,27 @@ impl Environment {
         }
     }
     
-    pub fn intern(&mut self, symbol: String, value: RefObject) -> RefObject {
+    pub fn intern(&mut self, symbol: Symbol, value: RefObject) -> RefObject {
         let mut symbols = self.symbols.write().unwrap();
-        symbols.insert(symbol.to_uppercase(), Arc::clone(&value));
+        symbols.insert(symbol, Arc::clone(&value));
         value
     }
-    pub fn unintern(&mut self, symbol: &String) {
+    /// The symbols bound in this scope, not including enclosing ones, sorted.
+    pub fn symbols(&self) -> Vec<String> {
+        let mut symbols = self
+            .symbols
+            .read()
+            .unwrap()
+            .keys()
+            .map(|symbol| symbol.name().to_string())
+            .collect::<Vec<_>>();
+        symbols.sort();
+        symbols
+    }
+
+    pub fn unintern(&mut self, symbol: Symbol) {
         let mut symbols = self.symbols.write().unwrap();
-        symbols.remove(symbol);
+        symbols.remove(&symbol);
     }
 }
 
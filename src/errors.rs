@@ -9,13 +9,62 @@ pub enum Error {
     NotCons,
     #[error("Expecting and integer.")]
     NotInteger,
+    #[error("Expecting a string.")]
+    NotString,
+    #[error("Expecting a character.")]
+    NotChar,
+    #[error("Expecting a vector.")]
+    NotVector,
+    #[error("Expecting a map.")]
+    NotMap,
+    #[error("A vector can't contain itself.")]
+    Cycle,
+    #[error("A map key can't contain a vector.")]
+    MutableKey,
+    #[error("Expecting a foreign object of type {0}.")]
+    NotForeign(&'static str),
+    #[error("Expecting a proper list.")]
+    NotProperList,
     #[error("Not a symbol.")]
     NotSymbol,
+    #[error("Unbound symbol {0}.")]
+    Unbound(String),
+    #[error("Symbol {0} is already defined.")]
+    AlreadyDefined(String),
+    #[error("Exit requested with status {0}.")]
+    Exit(i32),
+    #[error("Incomplete input.")]
+    Incomplete,
+    #[error("Syntax error: {0}.")]
+    Syntax(String),
+    #[error("Expecting a function or operator.")]
+    NotFunction,
+    #[error("Wrong number of arguments.")]
+    WrongArgumentCount,
+    #[error("Division by zero.")]
+    DivisionByZero,
+    #[error("Arithmetic overflow.")]
+    Overflow,
+    #[error("Argument out of the function's domain.")]
+    OutOfDomain,
+    #[error("Value can't be represented as a Lustre object.")]
+    NotRepresentable,
+    #[error("Missing field {0}.")]
+    MissingField(String),
+    #[error("Unknown variant {0}.")]
+    UnknownVariant(String),
+    #[error("Evaluation task failed: {0}.")]
+    TaskFailed(String),
     #[error("Input error.")]
     InputError {
         #[from]
         source: rustf8::Utf8IteratorError,
     },
+    #[error("I/O error: {source}.")]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
     #[error("Parsing error.")]
     ParseError {
         #[from]
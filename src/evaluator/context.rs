@@ -0,0 +1,83 @@
+use crate::environment::RefEnvironment;
+use crate::object::{RefObject, ResultRefObject};
+use crate::symbol::Symbol;
+
+use std::future::Future;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+pub type Output = Arc<Mutex<dyn Write + Send>>;
+
+/// The special form names, spelled like the builtins of the environment's
+/// `Case`, e.g. `IF` or `if` but not both.
+#[derive(Clone, Copy)]
+pub struct SpecialForms {
+    pub quote: Symbol,
+    pub conditional: Symbol,
+    pub lambda: Symbol,
+    pub def: Symbol,
+}
+
+/// What a native operator can reach while it runs: the environment of the
+/// call, the evaluator, the output sink and the runtime executing the code.
+#[derive(Clone)]
+pub struct Context {
+    pub environment: RefEnvironment,
+    pub output: Output,
+    pub runtime: Handle,
+    pub special_forms: SpecialForms,
+}
+
+impl Context {
+    /// A context writing to standard output.
+    pub fn new(environment: RefEnvironment, runtime: Handle) -> Self {
+        let case = environment.case();
+        Self {
+            environment,
+            output: Arc::new(Mutex::new(std::io::stdout())),
+            runtime,
+            special_forms: SpecialForms {
+                quote: case.builtin("QUOTE"),
+                conditional: case.builtin("IF"),
+                lambda: case.builtin("LAMBDA"),
+                def: case.builtin("DEF"),
+            },
+        }
+    }
+
+    /// A context writing to standard output and running on the tokio runtime
+    /// of the caller. Panics when called outside of a runtime.
+    pub fn current(environment: RefEnvironment) -> Self {
+        Self::new(environment, Handle::current())
+    }
+
+    pub fn with_output(self, output: Output) -> Self {
+        Self { output, ..self }
+    }
+
+    /// The same context evaluating in another environment, e.g. a lambda scope.
+    pub fn with_environment(&self, environment: RefEnvironment) -> Self {
+        Self {
+            environment,
+            ..self.clone()
+        }
+    }
+
+    pub async fn eval(&self, obj: &RefObject) -> ResultRefObject {
+        super::parallel_eval(obj, self.clone()).await
+    }
+
+    pub async fn apply(&self, function: RefObject, arguments: RefObject) -> ResultRefObject {
+        super::apply(function, arguments, self.clone()).await
+    }
+
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.runtime.spawn(future)
+    }
+}
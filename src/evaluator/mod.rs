@@ -1,84 +1,108 @@
+mod context;
 pub mod operators;
 
+pub use context::{Context, Output, SpecialForms};
+
 use crate::environment::RefEnvironment;
+use crate::errors::{self, Error};
 use std::sync::Arc;
 use crate::object::{destructure_list, nil, not_nil, result_nil, symbol_value, Object, RefObject, ResultRefObject};
+#[cfg(feature = "blocking")]
 use tokio::runtime::Runtime;
 use async_recursion::async_recursion;
 
+/// Evaluates on the caller's executor, e.g. from a service already running
+/// inside a tokio runtime. Build the context with `Context::current`.
+pub async fn eval_async(obj: &RefObject, context: Context) -> ResultRefObject {
+    parallel_eval(obj, context).await
+}
+
+#[cfg(feature = "blocking")]
 pub fn eval(obj: &RefObject, environment: RefEnvironment, rt: &mut Runtime) -> ResultRefObject {
+    let context = Context::new(environment, rt.handle().clone());
+    eval_with_context(obj, context, rt)
+}
+
+/// Blocks on `rt` until the evaluation finishes. Panics if called from within
+/// an asynchronous context; use `eval_async` there.
+#[cfg(feature = "blocking")]
+pub fn eval_with_context(obj: &RefObject, context: Context, rt: &mut Runtime) -> ResultRefObject {
     rt.block_on(async move {
-        parallel_eval(obj, environment).await
+        parallel_eval(obj, context).await
     })
 }
 
 #[async_recursion]
-async fn parallel_eval(obj: &RefObject, environment: RefEnvironment ) -> ResultRefObject {
+async fn parallel_eval(obj: &RefObject, context: Context) -> ResultRefObject {
     match obj.as_ref() {
         None => result_nil(),
         Some(Object::Cons(car, cdr)) => {
             if let Some(Object::Symbol(s)) = car.as_ref() {
-                if s == "IF" {
+                let forms = context.special_forms;
+                if *s == forms.conditional {
                     let (test, cdr) = destructure_list(cdr)?;
-                    let (true_expr, cdr) = destructure_list(&cdr)?;
-                    let (false_expr, _) = destructure_list(&cdr)?;
-                    if not_nil(&parallel_eval(test, environment.clone()).await?) {
-                        parallel_eval(true_expr, environment.clone()).await
+                    let (true_expr, cdr) = destructure_list(cdr)?;
+                    let (false_expr, _) = destructure_list(cdr)?;
+                    if not_nil(&parallel_eval(test, context.clone()).await?) {
+                        parallel_eval(true_expr, context.clone()).await
                     } else {
-                        parallel_eval(false_expr, environment.clone()).await
+                        parallel_eval(false_expr, context.clone()).await
                     }
-                } else if s == "QUOTE" {
+                } else if *s == forms.quote {
                     let (car, _) = destructure_list(cdr)?;
                     Ok(Arc::clone(car))
-                } else if s == "LAMBDA" {
+                } else if *s == forms.lambda {
                     lambda(cdr)
-                } else if s == "DEF" {
+                } else if *s == forms.def {
                     let (name, cdr) = destructure_list(cdr)?;
                     let (value, _) = destructure_list(cdr)?;
-                    let name = parallel_eval(name, environment.clone()).await?;
+                    let name = parallel_eval(name, context.clone()).await?;
                     let search_result = {
-                        let env = environment.0.read().unwrap();
-                        env.find_symbol(&symbol_value(&name)?)
+                        let env = context.environment.0.read().unwrap();
+                        env.find_symbol(symbol_value(&name)?)
                     };
-                    if let None = search_result {
-                        let value = parallel_eval(value, environment.clone()).await?;
-                        Ok(environment.0.write().unwrap().intern(symbol_value(&name)?, value))
+                    if search_result.is_none() {
+                        let value = parallel_eval(value, context.clone()).await?;
+                        Ok(context.environment.0.write().unwrap().intern(symbol_value(&name)?, value))
                     } else {
-                        panic!("Not allowed to redefine symbol.")
+                        Err(Error::AlreadyDefined(symbol_value(&name)?.to_string()))
                     }
                 } else {
-                    let car_eval = parallel_eval(car, environment.clone());
-                    let cdr_eval = parallel_eval_list(cdr, environment.clone());
-                    apply(car_eval.await?, cdr_eval.await?, environment).await
+                    let car_eval = parallel_eval(car, context.clone());
+                    let cdr_eval = parallel_eval_list(cdr, context.clone());
+                    apply(car_eval.await?, cdr_eval.await?, context).await
                 }
             } else {
-                let car_eval = parallel_eval(car, environment.clone());
-                let cdr_eval = parallel_eval_list(cdr, environment.clone());
-                apply(car_eval.await?, cdr_eval.await?, environment).await
+                let car_eval = parallel_eval(car, context.clone());
+                let cdr_eval = parallel_eval_list(cdr, context.clone());
+                apply(car_eval.await?, cdr_eval.await?, context).await
             }
         }
-        Some(Object::Symbol(s)) => match environment.0.read().unwrap().find_symbol(s) {
+        Some(Object::Symbol(s)) => match context.environment.0.read().unwrap().find_symbol(*s) {
             Some(v) => Ok(Arc::clone(&v)),
-            _ => panic!("Unbound!"),
+            _ => Err(Error::Unbound(s.to_string())),
         },
         _ => Ok(Arc::clone(obj)),
     }
 }
 
 #[async_recursion]
-async fn parallel_eval_list(obj: &RefObject, environment: RefEnvironment) -> ResultRefObject {
+async fn parallel_eval_list(obj: &RefObject, context: Context) -> ResultRefObject {
     let mut next = obj.clone();
     let mut result:RefObject = nil();
     let mut handles: Vec<_> = Vec::new();
     while not_nil(&next) {
         let (car, cdr) = destructure_list(&next)?;
         let car = car.clone();
-        let env = environment.clone();
-        handles.push(async move { parallel_eval(&car, env).await });
+        let context = context.clone();
+        handles.push(async move { parallel_eval(&car, context).await });
         next = cdr.clone();
     }
 
-    let handles = handles.into_iter().map(tokio::spawn).collect::<Vec<_>>();
+    let handles = handles
+        .into_iter()
+        .map(|handle| context.spawn(handle))
+        .collect::<Vec<_>>();
 
     let mut partials = futures::future::join_all(handles).await;
 
@@ -95,18 +119,15 @@ fn lambda(obj: &RefObject) -> ResultRefObject {
     Object::Lambda(params.clone(), expression.clone()).into()
 }
 
+/// Applies a lambda or operator to a list of already evaluated arguments.
+/// This is also how operators call back into user supplied functions.
 #[async_recursion]
-async fn apply(function: RefObject, cdr: RefObject, environment: RefEnvironment) -> ResultRefObject {
-    match function
-        .as_ref()
-        .as_ref()
-        .expect("Expecting a value, instead got nil or other None value.")
-    {
-        Object::Lambda(parameters, expression) => {
-            let values = parallel_eval_list(&cdr, environment.clone()).await?;
-            let mut next_value = &values;
+pub async fn apply(function: RefObject, cdr: RefObject, context: Context) -> ResultRefObject {
+    match function.as_ref() {
+        Some(Object::Lambda(parameters, expression)) => {
+            let mut next_value = &cdr;
             let mut next_param = parameters;
-            let scope = RefEnvironment::from(&environment);
+            let scope = RefEnvironment::from(&context.environment);
             while not_nil(next_value) && not_nil(next_param) {
                 let (value, cdr_value) = destructure_list(next_value)?;
                 let (param, cdr_param) = destructure_list(next_param)?;
@@ -114,19 +135,45 @@ async fn apply(function: RefObject, cdr: RefObject, environment: RefEnvironment)
                 next_value = cdr_value;
                 next_param = cdr_param;
             }
-            let result = parallel_eval(expression, scope).await;
+            let result = parallel_eval(expression, context.with_environment(scope)).await;
             Ok(result?)
         }
-        Object::Operator(_, f) => f(cdr),
-        _ => panic!("Expected operator or function."),
+        Some(Object::Operator(_, f)) => f(cdr),
+        Some(Object::Closure(_, f)) => f(cdr, &context),
+        Some(Object::AsyncOperator(_, f)) => f(cdr, context).await,
+        _ => Err(Error::NotFunction),
     }
 }
 
-#[cfg(test)]
+/// Applies `function` to each argument list in its own task, like
+/// `parallel_eval_list` does for the elements of a form.
+pub async fn parallel_apply(
+    function: &RefObject,
+    argument_lists: Vec<RefObject>,
+    context: Context,
+) -> errors::Result<Vec<RefObject>> {
+    let handles = argument_lists
+        .into_iter()
+        .map(|arguments| {
+            let function = function.clone();
+            let task_context = context.clone();
+            context.spawn(async move { apply(function, arguments, task_context).await })
+        })
+        .collect::<Vec<_>>();
+
+    futures::future::join_all(handles)
+        .await
+        .into_iter()
+        .map(|value| value.map_err(|error| Error::TaskFailed(error.to_string()))?)
+        .collect()
+}
+
+#[cfg(all(test, feature = "blocking"))]
 mod tests {
     use super::*;
     use crate::environment;
     use crate::reader;
+    use crate::symbol;
     use std::io::prelude::*;
     use std::io::Cursor;
 
@@ -137,7 +184,7 @@ mod tests {
             let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
             let mut environment = RefEnvironment::new();
             operators::initialize_operators(&mut environment);
-            let value = reader::Reader::new(tokenizer).read().unwrap();
+            let value = reader::Reader::new(tokenizer).read().unwrap().unwrap();
             eprintln!("reader: {:?}", value);
             if let Some(_) = value.as_ref() {
                 let result = eval(&value, environment, &mut rt);
@@ -154,6 +201,50 @@ mod tests {
         };
     }
 
+    /// An environment with the builtins, evaluating whole programs and
+    /// keeping what they print.
+    struct Fixture {
+        environment: RefEnvironment,
+        output: Arc<std::sync::Mutex<Vec<u8>>>,
+        rt: Runtime,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let environment = RefEnvironment::new();
+            operators::initialize_operators(&environment);
+            Self {
+                environment,
+                output: Arc::new(std::sync::Mutex::new(Vec::new())),
+                rt: Runtime::new().unwrap(),
+            }
+        }
+
+        /// Evaluates every form in `input`, returning the last value.
+        fn eval(&mut self, input: &str) -> ResultRefObject {
+            let context = Context::new(self.environment.clone(), self.rt.handle().clone())
+                .with_output(self.output.clone());
+            let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
+            let mut result = nil();
+            for ast in reader::Reader::new(tokenizer) {
+                result = eval_with_context(&ast?, context.clone(), &mut self.rt)?;
+            }
+            Ok(result)
+        }
+
+        fn printed(&self) -> String {
+            String::from_utf8(self.output.lock().unwrap().clone()).unwrap()
+        }
+
+        /// Like `eval`, printing the value, nil as `NIL`.
+        fn run(&mut self, input: &str) -> crate::errors::Result<String> {
+            Ok(match self.eval(input)?.as_ref() {
+                Some(value) => format!("{}", value),
+                None => String::from("NIL"),
+            })
+        }
+    }
+
     #[test]
     fn eval_test_1() {
         test_eval! {
@@ -183,10 +274,10 @@ mod tests {
                 #[rustfmt::skip]
                 assert_eq!(
                     Cons(
-                        Symbol("A".to_string()).into(),
+                        Symbol(symbol::Symbol::intern("A")).into(),
                         Cons(
-                            Symbol("B".to_string()).into(),
-                            Cons(Symbol("C".to_string()).into(),
+                            Symbol(symbol::Symbol::intern("B")).into(),
+                            Cons(Symbol(symbol::Symbol::intern("C")).into(),
                                  nil()).into()
                         ).into()
                     ),
@@ -242,18 +333,13 @@ mod tests {
         let input = "(def 'add (lambda (x y) (+ x y))) (add 13 21)";
         let mut rt = Runtime::new().unwrap();
         let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
-        let mut environment = RefEnvironment::new();
-        operators::initialize_operators(&mut environment);
-        let mut reader = reader::Reader::new(tokenizer);
+        let environment = RefEnvironment::new();
+        operators::initialize_operators(&environment);
+        let reader = reader::Reader::new(tokenizer);
         let mut result: ResultRefObject = result_nil();
-        loop {
-            let ast = reader.read().unwrap();
-            eprintln!("reader: {:?}", ast);
-            if ast.as_ref().is_some() {
-                result = eval(&ast, environment.clone(), &mut rt);
-            } else {
-                break;
-            }
+        for ast in reader {
+            let ast = ast.unwrap();
+            result = eval(&ast, environment.clone(), &mut rt);
         }
 
         assert_eq!(
@@ -276,16 +362,11 @@ mod tests {
         let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
         let environment = environment::RefEnvironment::new();
         operators::initialize_operators(&environment);
-        let mut reader = reader::Reader::new(tokenizer);
+        let reader = reader::Reader::new(tokenizer);
         let mut result: ResultRefObject = result_nil();
-        loop {
-            let ast = reader.read().unwrap();
-            eprintln!("reader: {:?}", ast);
-            if ast.as_ref().is_some() {
-                result = eval(&ast, environment.clone(), &mut rt);
-            } else {
-                break;
-            }
+        for ast in reader {
+            let ast = ast.unwrap();
+            result = eval(&ast, environment.clone(), &mut rt);
         }
         eprintln!("result: {:?}", result);
         assert_eq!(
@@ -293,4 +374,432 @@ mod tests {
             *result.unwrap().as_ref().as_ref().unwrap()
         );
     }
+
+    #[test]
+    fn eval_test_9() {
+        macro_rules! assert_integer {
+            ($code:expr, $value:expr) => {
+                test_eval! {
+                    $code;
+                    with obj {
+                        assert_eq!(Object::Integer($value), *obj);
+                    }
+                }
+            };
+        }
+        assert_integer!("(mod (- 0 7) 3)", 2);
+        assert_integer!("(mod 7 (- 0 3))", -2);
+        assert_integer!("(rem (- 0 7) 3)", -1);
+        assert_integer!("(abs (- 0 5))", 5);
+        assert_integer!("(min 4 2 9)", 2);
+        assert_integer!("(max 4 2 9)", 9);
+        assert_integer!("(expt 2 10)", 1024);
+        assert_integer!("(sqrt 17)", 4);
+        assert_integer!("(gcd 12 18 27)", 3);
+        assert_integer!("(lcm 4 6 10)", 60);
+        assert_integer!("(if (evenp 4) (if (oddp 4) 0 1) 0)", 1);
+        assert_integer!("(if (zerop 0) 1 0)", 1);
+        assert_integer!("(logand 12 10)", 8);
+        assert_integer!("(logior 12 10)", 14);
+        assert_integer!("(logxor 12 10)", 6);
+        assert_integer!("(ash 1 4)", 16);
+        assert_integer!("(ash (- 0 16) (- 0 2))", -4);
+    }
+
+    #[test]
+    fn eval_test_10() {
+        test_eval! {
+            "(if (and (< 1 2 3) (not (< 1 3 2)))
+                 (if (and (<= -1 -1 0) (>= 3 3 -2))
+                     (if (and (= 4 4 4) (/= 1 2 3))
+                         (if (or (> 3 2 2) (/= 1 2 1)) \"WRONG\" \"OK\")
+                         \"WRONG\")
+                     \"WRONG\")
+                 \"WRONG\")";
+            with obj {
+                assert_eq!(Object::IString("OK".to_string()), *obj);
+            }
+        }
+    }
+
+    #[test]
+    fn eval_test_11() {
+        test_eval! {
+            "(if (and (eq 'a 'a) (not (eq '(1 2) '(1 2))))
+                 (if (and (eql 3 3) (not (eql '(3) '(3))))
+                     (if (and (equal '(1 (2 \"x\")) '(1 (2 \"x\"))) (not (equal '(1 2) '(1 3))))
+                         (if (and (equal car car) (not (equal car cdr))) \"OK\" \"WRONG\")
+                         \"WRONG\")
+                     \"WRONG\")
+                 \"WRONG\")";
+            with obj {
+                assert_eq!(Object::IString("OK".to_string()), *obj);
+            }
+        }
+    }
+
+    #[test]
+    fn eval_test_12() {
+        macro_rules! assert_printed {
+            ($code:expr, $printed:expr) => {
+                test_eval! {
+                    $code;
+                    with obj {
+                        assert_eq!($printed, format!("{}", obj));
+                    }
+                }
+            };
+        }
+        assert_printed!("(cons 1 '(2 3))", "( 1 2 3 )");
+        assert_printed!("(list 1 (+ 1 1) '(3))", "( 1 2 ( 3 ) )");
+        assert_printed!("(append '(1 2) '() '(3) '(4 5))", "( 1 2 3 4 5 )");
+        assert_printed!("(reverse '(1 2 3))", "( 3 2 1 )");
+        assert_printed!("(length '(a b c d))", "4");
+        assert_printed!("(nth 2 '(a b c d))", "C");
+        assert_printed!("(nthcdr 2 '(a b c d))", "( C D )");
+        assert_printed!("(last '(a b c d))", "( D )");
+        assert_printed!("(member 3 '(1 2 3 4))", "( 3 4 )");
+        assert_printed!("(assoc 'b '((a 1) (b 2)))", "( B 2 )");
+        assert_printed!("(copy-list '(1 (2) 3))", "( 1 ( 2 ) 3 )");
+    }
+
+    #[test]
+    fn eval_test_13() {
+        let mut rt = Runtime::new().unwrap();
+        let environment = RefEnvironment::new();
+        operators::initialize_operators(&environment);
+        let input = "(length (cons 1 2))";
+        let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
+        let ast = reader::Reader::new(tokenizer).read().unwrap().unwrap();
+        match eval(&ast, environment, &mut rt) {
+            Err(crate::errors::Error::NotProperList) => (),
+            result => panic!("Expected an improper list error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn eval_test_14() {
+        macro_rules! assert_printed {
+            ($code:expr, $printed:expr) => {
+                test_eval! {
+                    $code;
+                    with obj {
+                        assert_eq!($printed, format!("{}", obj));
+                    }
+                }
+            };
+        }
+        assert_printed!("(mapcar (lambda (x) (* x x)) '(1 2 3))", "( 1 4 9 )");
+        assert_printed!("(mapcar + '(1 2 3) '(10 20))", "( 11 22 )");
+        assert_printed!("(mapcar car '((a 1) (b 2)))", "( A B )");
+        assert_printed!("(filter oddp '(1 2 3 4 5))", "( 1 3 5 )");
+        assert_printed!("(reduce + '(1 2 3 4))", "10");
+        assert_printed!("(reduce - '(1 2 3) 10)", "4");
+        assert_printed!("(not (for-each (lambda (x) x) '(1 2)))", "1");
+        assert_printed!("(apply + 1 2 '(3 4))", "10");
+        assert_printed!("(funcall (lambda (l) (car l)) '(7 8))", "7");
+        assert_printed!("(sort '(3 1 2 5 4) <)", "( 1 2 3 4 5 )");
+        assert_printed!("(sort '((b 1) (a 2) (b 0)) (lambda (x y) (< (nth 1 x) (nth 1 y))))",
+                        "( ( B 0 ) ( B 1 ) ( A 2 ) )");
+    }
+
+    #[test]
+    fn eval_test_15() {
+        let input = "(sleep 1) (print (eval '(+ 1 2)) \"done\")";
+        let mut rt = Runtime::new().unwrap();
+        let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
+        let environment = RefEnvironment::new();
+        operators::initialize_operators(&environment);
+        let output = Arc::new(std::sync::Mutex::new(Vec::<u8>::new()));
+        let context = Context::new(environment, rt.handle().clone()).with_output(output.clone());
+        let reader = reader::Reader::new(tokenizer);
+        let mut result: ResultRefObject = result_nil();
+        for ast in reader {
+            let ast = ast.unwrap();
+            result = eval_with_context(&ast, context.clone(), &mut rt);
+        }
+
+        assert_eq!(
+            Object::IString("done".to_string()),
+            *result.unwrap().as_ref().as_ref().unwrap()
+        );
+        assert_eq!("3\n\"done\"\n", String::from_utf8(output.lock().unwrap().clone()).unwrap());
+    }
+
+    #[test]
+    fn eval_test_16() {
+        use std::sync::atomic::{AtomicI32, Ordering};
+
+        let mut rt = Runtime::new().unwrap();
+        let environment = RefEnvironment::new();
+        operators::initialize_operators(&environment);
+        let counter = Arc::new(AtomicI32::new(100));
+        let state = counter.clone();
+        environment.register("next-id", move |args, _| {
+            let step = crate::object::integer_value(destructure_list(&args)?.0)?;
+            Object::Integer(state.fetch_add(step, Ordering::SeqCst)).into()
+        });
+
+        let input = "(+ (next-id 1) (next-id 1))";
+        let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
+        let ast = reader::Reader::new(tokenizer).read().unwrap().unwrap();
+        let result = eval(&ast, environment.clone(), &mut rt).unwrap();
+        assert_eq!(Object::Integer(201), *result.as_ref().as_ref().unwrap());
+        assert_eq!(102, counter.load(Ordering::SeqCst));
+
+        environment.unregister("next-id");
+        assert!(environment.0.read().unwrap().find_symbol(symbol::Symbol::intern("NEXT-ID")).is_none());
+    }
+
+    #[test]
+    fn eval_test_17() {
+        fn repeat(n: i64, text: String) -> crate::errors::Result<Vec<i64>> {
+            Ok(vec![text.len() as i64; n as usize])
+        }
+
+        let mut lisp = Fixture::new();
+        lisp.environment.register_function("repeat", repeat);
+
+        assert_eq!("( 4 4 4 )", lisp.run("(repeat 3 \"abcd\")").unwrap());
+        assert!(matches!(lisp.run("(repeat 3)"), Err(Error::WrongArgumentCount)));
+        assert!(matches!(lisp.run("(repeat \"abcd\" 3)"), Err(Error::NotInteger)));
+    }
+
+    #[test]
+    fn eval_test_18() {
+        use crate::object::{foreign, is_foreign, Foreign};
+
+        struct Connection {
+            name: String,
+        }
+
+        let mut lisp = Fixture::new();
+        let db = foreign(Connection {
+            name: String::from("main"),
+        });
+        lisp.environment.0.write().unwrap().intern(symbol::Symbol::intern("DB"), db);
+        lisp.environment.register_function("db-name", |c: Foreign<Connection>| c.0.name.clone());
+
+        let result = lisp.run("(if (foreignp db \"Connection\") (db-name db) \"WRONG\")");
+        assert_eq!("\"main\"", result.unwrap());
+        assert!(matches!(lisp.run("(db-name 1)"), Err(Error::NotForeign(_))));
+        let db = lisp.eval("(car (list db))").unwrap();
+        assert!(is_foreign::<Connection>(&db));
+        assert!(format!("{}", db.as_ref().as_ref().unwrap()).starts_with("#<FOREIGN "));
+    }
+
+    #[test]
+    fn eval_test_19() {
+        let mut lisp = Fixture::new();
+        assert_eq!("( #\\a #\\space #\\A )", lisp.run("'(#\\a #\\space #\\x41)").unwrap());
+        assert_eq!("955", lisp.run("(char->integer #\\λ)").unwrap());
+        assert_eq!("#\\Λ", lisp.run("(char-upcase (integer->char 955))").unwrap());
+        assert_eq!("#\\ß", lisp.run("(char-upcase #\\ß)").unwrap());
+        assert_eq!("#\\é", lisp.run("(char \"héllo\" 1)").unwrap());
+        assert_eq!("1", lisp.run("(char-alphabetic? (char \"héllo\" 1))").unwrap());
+        assert_eq!("1", lisp.run("(eql #\\newline (integer->char 10))").unwrap());
+        assert!(matches!(lisp.run("(char \"abc\" 3)"), Err(Error::OutOfDomain)));
+        assert!(matches!(lisp.run("(integer->char 55296)"), Err(Error::OutOfDomain)));
+        assert!(matches!(lisp.run("(char-upcase \"a\")"), Err(Error::NotChar)));
+    }
+
+    #[test]
+    fn eval_test_20() {
+        let mut lisp = Fixture::new();
+        assert_eq!("5", lisp.run("(string-length \"κόσμε\")").unwrap());
+        assert_eq!("\"όσ\"", lisp.run("(substring \"κόσμε\" 1 3)").unwrap());
+        assert_eq!("\"με\"", lisp.run("(substring \"κόσμε\" 3)").unwrap());
+        assert_eq!("\"ΚΌΣΜΕ world\"", lisp.run("(string-append (string-upcase \"κόσμε\") \" \" \"world\")").unwrap());
+        assert_eq!("\"straße\"", lisp.run("(string-downcase \"STRAßE\")").unwrap());
+        assert_eq!("( \"a\" \"b\" \"c\" )", lisp.run("(string-split \"  a b\tc \")").unwrap());
+        assert_eq!("\"a-b--c\"", lisp.run("(string-join (string-split \"a,b,,c\" \",\") \"-\")").unwrap());
+        assert_eq!("\"x y\"", lisp.run("(string-trim \" \tx y\n\")").unwrap());
+        assert_eq!("2", lisp.run("(string-contains \"κόσμε\" \"σμ\")").unwrap());
+        assert_eq!("NIL", lisp.run("(string-contains \"κόσμε\" \"x\")").unwrap());
+        assert_eq!("-42", lisp.run("(string->number \" -42 \")").unwrap());
+        assert_eq!("NIL", lisp.run("(string->number \"4x\")").unwrap());
+        assert_eq!("\"-7\"", lisp.run("(number->string -7)").unwrap());
+        assert_eq!("\"FOO\"", lisp.run("(symbol->string 'foo)").unwrap());
+        assert_eq!("1", lisp.run("(eq (string->symbol \"FOO\") 'foo)").unwrap());
+        assert_eq!("1", lisp.run("(string< \"abc\" \"abd\" \"κ\")").unwrap());
+        assert_eq!("NIL", lisp.run("(string/= \"a\" \"b\" \"a\")").unwrap());
+        assert!(matches!(lisp.run("(substring \"abc\" 2 4)"), Err(Error::OutOfDomain)));
+        assert!(matches!(lisp.run("(string-length 1)"), Err(Error::NotString)));
+    }
+
+    #[test]
+    fn eval_test_21() {
+        let mut lisp = Fixture::new();
+        assert_eq!("( 1 . 2 )", lisp.run("(cons 1 2)").unwrap());
+        assert_eq!("( 2 . 3 )", lisp.run("(last '(1 2 . 3))").unwrap());
+        assert_eq!("( 1 2 . 3 )", lisp.run("(copy-list '(1 2 . 3))").unwrap());
+        assert_eq!("3", lisp.run("(nthcdr 2 '(1 2 . 3))").unwrap());
+        assert_eq!("( B . 2 )", lisp.run("(assoc 'b '((a . 1) (b . 2)))").unwrap());
+        assert_eq!("( 1 2 . 3 )", lisp.run("(append '(1) '(2 . 3))").unwrap());
+        assert_eq!("1", lisp.run("(atom (cdr '(1 . 2)))").unwrap());
+        assert_eq!("NIL", lisp.run("(consp ())").unwrap());
+        assert!(matches!(lisp.run("(length '(1 . 2))"), Err(Error::NotProperList)));
+    }
+
+    #[test]
+    fn eval_test_22() {
+        let mut lisp = Fixture::new();
+        assert_eq!("#( 1 ( A B ) \"c\" )", lisp.run("#(1 (a b) \"c\")").unwrap());
+        lisp.run("(def 'v (make-vector 3 0))").unwrap();
+        lisp.run("(def 'alias (car (list v)))").unwrap();
+        assert_eq!("7", lisp.run("(vector-set! alias 1 7)").unwrap());
+        assert_eq!("#( 0 7 0 )", lisp.run("v").unwrap());
+        assert_eq!("7", lisp.run("(vector-ref v 1)").unwrap());
+        assert_eq!("3", lisp.run("(vector-length v)").unwrap());
+        assert_eq!("( 1 2 )", lisp.run("(vector->list (vector 1 2))").unwrap());
+        assert_eq!("#( 1 2 )", lisp.run("(list->vector '(1 2))").unwrap());
+        assert_eq!("1", lisp.run("(equal [1 2] (vector 1 2))").unwrap());
+        assert_eq!("#( 11 22 )", lisp.run("(vector-map + [1 2] #(10 20 30))").unwrap());
+        assert_eq!("#()", lisp.run("(vector-map (lambda (x) x) [])").unwrap());
+        assert!(matches!(lisp.run("(vector-ref v 3)"), Err(Error::OutOfDomain)));
+        assert!(matches!(lisp.run("(vector-length '(1))"), Err(Error::NotVector)));
+        lisp.run("(def 'w (vector 1 (list 2 v)))").unwrap();
+        for input in &["(vector-set! v 0 v)", "(vector-set! v 0 w)", "(vector-set! v 0 (put {} (quote a) w))"] {
+            assert!(matches!(lisp.run(input), Err(Error::Cycle)), "{}", input);
+        }
+        assert_eq!("#( 1 #( 0 7 0 ) )", lisp.run("(vector-set! w 1 v) w").unwrap());
+        assert!(matches!(lisp.run("(make-vector 100000000)"), Err(Error::OutOfDomain)));
+        // Arguments evaluate concurrently, and only one of the stores may win.
+        for _ in 0..100 {
+            let input = "((lambda (a b) (list (vector-set! a 0 b) (vector-set! b 0 a))) (vector 0) (vector 0))";
+            assert!(matches!(lisp.run(input), Err(Error::Cycle)));
+        }
+    }
+
+    #[test]
+    fn eval_test_23() {
+        let mut lisp = Fixture::new();
+        lisp.run("(def 'm {a 1 \"b\" (2 3) (4) 5})").unwrap();
+        assert_eq!("1", lisp.run("(get m 'a)").unwrap());
+        assert_eq!("( 2 3 )", lisp.run("(get m \"b\")").unwrap());
+        assert_eq!("5", lisp.run("(get m (list 4))").unwrap());
+        assert_eq!("NIL", lisp.run("(get m 'c)").unwrap());
+        assert_eq!("0", lisp.run("(get m 'c 0)").unwrap());
+        assert_eq!("{ C 3 }", lisp.run("(dissoc (put m 'c 3) 'a \"b\" '(4))").unwrap());
+        assert_eq!("NIL", lisp.run("(contains? m 'c)").unwrap());
+        assert_eq!("3", lisp.run("(length (keys m))").unwrap());
+        assert_eq!("( 2 )", lisp.run("(vals {x 2})").unwrap());
+        assert_eq!("1", lisp.run("(equal (merge {a 1 b 1} {b 2}) {b 2 a 1})").unwrap());
+        assert_eq!("1", lisp.run("(get {{a 1 b 2} 1} (put {b 2} 'a 1))").unwrap());
+        assert_eq!("( B . 2 )", lisp.run("(assoc 'b '((a . 1) (b . 2)))").unwrap());
+        assert_eq!("{}", lisp.run("(dissoc {a 1} 'a)").unwrap());
+        assert_eq!("1", lisp.run("(equal (keys {a 1 b 2 c 3}) (keys {c 3 b 2 a 1}))").unwrap());
+        for input in &["(put m 'c)", "(put m 'c 1 'd)", "(assoc 'c 1 2)"] {
+            assert!(matches!(lisp.run(input), Err(Error::WrongArgumentCount)), "{}", input);
+        }
+        assert!(matches!(lisp.run("(get '(a 1) 'a)"), Err(Error::NotMap)));
+
+        lisp.run("(def 'v [1]) (def 'n (put {} 'k v))").unwrap();
+        for input in &["(put n v 1)", "(put n (list 'x v) 1)", "(put n n 1)"] {
+            assert!(matches!(lisp.run(input), Err(Error::MutableKey)), "{}", input);
+        }
+        lisp.run("(vector-set! v 0 2)").unwrap();
+        assert_eq!("#( 2 )", lisp.run("(get n 'k)").unwrap());
+        assert_eq!("1", lisp.run("(equal n {k [2]})").unwrap());
+    }
+
+    #[test]
+    fn eval_test_24() {
+        let mut lisp = Fixture::new();
+        assert_eq!(":RED", lisp.run(":red").unwrap());
+        assert_eq!("( :A 1 )", lisp.run("(list :a 1)").unwrap());
+        assert_eq!("1", lisp.run("(eq :a (car '(:A)))").unwrap());
+        assert_eq!("NIL", lisp.run("(eq :a 'a)").unwrap());
+        assert_eq!("2", lisp.run("(get {:x 1 :y 2} :y)").unwrap());
+        assert_eq!("1", lisp.run("(keywordp :key)").unwrap());
+        assert_eq!("NIL", lisp.run("(keywordp 'key)").unwrap());
+    }
+
+    #[test]
+    fn eval_test_25() {
+        let mut lisp = Fixture::new();
+        assert_eq!("1", lisp.run("(eq (intern \"ABC\") 'abc)").unwrap());
+        assert_eq!("NIL", lisp.run("(eq (intern \"abc\") 'abc)").unwrap());
+        lisp.run("(def 'g (gensym \"TMP\"))").unwrap();
+        assert_eq!("1", lisp.run("(eq g g)").unwrap());
+        assert_eq!("NIL", lisp.run("(eq g (gensym \"TMP\"))").unwrap());
+        assert_eq!("NIL", lisp.run("(eq g (intern (symbol->string g)))").unwrap());
+        assert_eq!("\"TMP\"", lisp.run("(substring (symbol->string g) 0 3)").unwrap());
+        assert_eq!("5", lisp.run("(def 'h (gensym)) (def h 5)").unwrap());
+        assert_eq!("5", lisp.run("(eval h)").unwrap());
+        assert_eq!("NIL", lisp.run("(eq h (gensym))").unwrap());
+    }
+
+    #[test]
+    fn eval_test_26() {
+        let mut lisp = Fixture::new();
+        assert_eq!("-5", lisp.run("(- 5)").unwrap());
+        assert_eq!("-2147483648", lisp.run("(- -2147483647 1)").unwrap());
+        assert_eq!("0", lisp.run("(mod -2147483648 -1)").unwrap());
+        assert_eq!("0", lisp.run("(rem -2147483648 -1)").unwrap());
+        assert_eq!("-1", lisp.run("(mod 7 -4)").unwrap());
+        for input in &["(/ 1 0)", "(/ 8 2 0)", "(mod 1 0)", "(rem 1 0)"] {
+            assert!(matches!(lisp.run(input), Err(Error::DivisionByZero)), "{}", input);
+        }
+        for input in &[
+            "(+ 2147483647 1)",
+            "(* 65536 32768)",
+            "(- -2147483648 1)",
+            "(- -2147483648)",
+            "(/ -2147483648 -1)",
+            "(abs -2147483648)",
+            "(expt 2 31)",
+            "(ash 1 31)",
+            "(lcm 65536 65537)",
+        ] {
+            assert!(matches!(lisp.run(input), Err(Error::Overflow)), "{}", input);
+        }
+        for input in &["(sqrt -1)", "(expt 2 -1)"] {
+            assert!(matches!(lisp.run(input), Err(Error::OutOfDomain)), "{}", input);
+        }
+    }
+
+    #[test]
+    fn eval_test_27() {
+        let mut lisp = Fixture::new();
+        lisp.environment.register("announce", |arguments, context| {
+            let (name, _) = destructure_list(&arguments)?;
+            let symbol = symbol_value(name)?;
+            let value = context.environment.0.read().unwrap().find_symbol(symbol);
+            let value = value.ok_or_else(|| Error::Unbound(symbol.to_string()))?;
+            writeln!(context.output.lock().unwrap(), "{} = {}", symbol, value.as_ref().as_ref().unwrap())?;
+            Ok(value)
+        });
+        assert_eq!("3", lisp.run("(def 'x 3) ((lambda (x) (announce 'x)) 3)").unwrap());
+        assert_eq!("X = 3\n", lisp.printed());
+        assert!(matches!(lisp.run("(announce 'y)"), Err(Error::Unbound(_))));
+    }
+
+    #[test]
+    fn eval_test_28() {
+        let mut lisp = Fixture::new();
+        lisp.environment.register("boom", |_, _| panic!("boom"));
+        assert!(matches!(lisp.run("(mapcar boom '(1 2))"), Err(Error::TaskFailed(_))));
+        assert_eq!("( 2 3 )", lisp.run("(mapcar (lambda (x) (+ x 1)) '(1 2))").unwrap());
+    }
+
+    #[test]
+    fn eval_test_29() {
+        let mut lisp = Fixture::new();
+        assert_eq!("1000000", lisp.run("(length (vector->list (make-vector 1000000 0)))").unwrap());
+    }
+
+    #[test]
+    fn eval_async_test() {
+        let mut rt = Runtime::new().unwrap();
+        let input = "(mapcar (lambda (x) (* x 2)) '(1 2 3))";
+        let tokenizer = reader::tokenizer::Tokenizer::new(Cursor::new(input).bytes());
+        let ast = reader::Reader::new(tokenizer).read().unwrap().unwrap();
+        let result = rt.block_on(async move {
+            let environment = RefEnvironment::new();
+            operators::initialize_operators(&environment);
+            eval_async(&ast, Context::current(environment)).await
+        });
+        assert_eq!("( 2 4 6 )", format!("{}", result.unwrap().as_ref().as_ref().unwrap()));
+    }
 }
@@ -1,8 +1,39 @@
-use crate::evaluator::RefEnvironment;
+use crate::errors::{self, Error};
+use crate::evaluator::{apply, parallel_apply, Context, RefEnvironment};
 use crate::object::*;
+use crate::symbol::Symbol;
 
+use futures::future::{BoxFuture, FutureExt};
+use std::convert::TryFrom;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use std::sync::Arc;
+fn integer_arguments(obj: &RefObject) -> errors::Result<Vec<i32>> {
+    let mut values = Vec::new();
+    let mut next = obj;
+    while not_nil(next) {
+        let (car, cdr) = destructure_list(next)?;
+        values.push(integer_value(car)?);
+        next = cdr;
+    }
+    Ok(values)
+}
+
+fn arguments(obj: &RefObject) -> errors::Result<Vec<RefObject>> {
+    list_elements(obj)
+}
+
+fn index_value(obj: &RefObject) -> errors::Result<usize> {
+    usize::try_from(integer_value(obj)?).map_err(|_| Error::OutOfDomain)
+}
+
+fn boolean(value: bool) -> ResultRefObject {
+    if value {
+        Object::Integer(1).into()
+    } else {
+        result_nil()
+    }
+}
 
 pub fn sum(obj: RefObject) -> ResultRefObject {
     if not_nil(&obj) {
@@ -10,8 +41,10 @@ pub fn sum(obj: RefObject) -> ResultRefObject {
         let mut next = &obj;
         while not_nil(next) {
             let (car, cdr) = destructure_list(next)?;
-            total += integer_value(&car)?;
-            next = &cdr;
+            total = total
+                .checked_add(integer_value(car)?)
+                .ok_or(Error::Overflow)?;
+            next = cdr;
         }
         Object::Integer(total).into()
     } else {
@@ -25,8 +58,10 @@ pub fn mult(obj: RefObject) -> ResultRefObject {
         let mut next = &obj;
         while not_nil(next) {
             let (car, cdr) = destructure_list(next)?;
-            total *= integer_value(&car)?;
-            next = &cdr;
+            total = total
+                .checked_mul(integer_value(car)?)
+                .ok_or(Error::Overflow)?;
+            next = cdr;
         }
         Object::Integer(total).into()
     } else {
@@ -37,12 +72,16 @@ pub fn mult(obj: RefObject) -> ResultRefObject {
 pub fn div(obj: RefObject) -> ResultRefObject {
     if not_nil(&obj) {
         let (car, cdr) = destructure_list(&obj)?;
-        let mut total = integer_value(&car)?;
+        let mut total = integer_value(car)?;
         let mut next = cdr;
         while not_nil(next) {
             let (car, cdr) = destructure_list(next)?;
-            total /= integer_value(&car)?;
-            next = &cdr;
+            let divisor = integer_value(car)?;
+            if divisor == 0 {
+                return Err(Error::DivisionByZero);
+            }
+            total = total.checked_div(divisor).ok_or(Error::Overflow)?;
+            next = cdr;
         }
         Object::Integer(total).into()
     } else {
@@ -50,15 +89,21 @@ pub fn div(obj: RefObject) -> ResultRefObject {
     }
 }
 
+/// Subtracts the rest from the first argument, or negates a single one.
 pub fn sub(obj: RefObject) -> ResultRefObject {
     if not_nil(&obj) {
         let (car, cdr) = destructure_list(&obj)?;
-        let mut total = integer_value(&car)?;
+        let mut total = integer_value(car)?;
+        if !not_nil(cdr) {
+            return Object::Integer(total.checked_neg().ok_or(Error::Overflow)?).into();
+        }
         let mut next = cdr;
         while not_nil(next) {
             let (car, cdr) = destructure_list(next)?;
-            total -= integer_value(&car)?;
-            next = &cdr;
+            total = total
+                .checked_sub(integer_value(car)?)
+                .ok_or(Error::Overflow)?;
+            next = cdr;
         }
         Object::Integer(total).into()
     } else {
@@ -68,8 +113,8 @@ pub fn sub(obj: RefObject) -> ResultRefObject {
 
 pub fn and(obj: RefObject) -> ResultRefObject {
     let (car1, cdr) = destructure_list(&obj)?;
-    let (car2, _) = destructure_list(&cdr)?;
-    if not_nil(&car1) && not_nil(&car2) {
+    let (car2, _) = destructure_list(cdr)?;
+    if not_nil(car1) && not_nil(car2) {
         Object::Integer(1).into()
     } else {
         result_nil()
@@ -78,8 +123,8 @@ pub fn and(obj: RefObject) -> ResultRefObject {
 
 pub fn or(obj: RefObject) -> ResultRefObject {
     let (car1, cdr) = destructure_list(&obj)?;
-    let (car2, _) = destructure_list(&cdr)?;
-    if not_nil(&car1) || not_nil(&car2) {
+    let (car2, _) = destructure_list(cdr)?;
+    if not_nil(car1) || not_nil(car2) {
         Object::Integer(1).into()
     } else {
         result_nil()
@@ -88,7 +133,7 @@ pub fn or(obj: RefObject) -> ResultRefObject {
 
 pub fn not(obj: RefObject) -> ResultRefObject {
     let (car1, _) = destructure_list(&obj)?;
-    if !not_nil(&car1) {
+    if !not_nil(car1) {
         Object::Integer(1).into()
     } else {
         result_nil()
@@ -115,34 +160,886 @@ pub fn cdr(obj: RefObject) -> ResultRefObject {
     }
 }
 
-pub fn greater_than(obj: RefObject) -> ResultRefObject {
-    let (car1, cdr) = destructure_list(&obj)?;
-    let (car2, _) = destructure_list(&cdr)?;
-    if integer_value(&car1)? > integer_value(&car2)? {
-        Object::Integer(1).into()
-    } else {
-        result_nil()
+fn chained(obj: &RefObject, test: fn(&i32, &i32) -> bool) -> ResultRefObject {
+    let values = integer_arguments(obj)?;
+    if values.is_empty() {
+        return Err(Error::WrongArgumentCount);
     }
+    boolean(values.windows(2).all(|pair| test(&pair[0], &pair[1])))
+}
+
+pub fn greater_than(obj: RefObject) -> ResultRefObject {
+    chained(&obj, i32::gt)
 }
 
 pub fn less_than(obj: RefObject) -> ResultRefObject {
-    let (car1, cdr) = destructure_list(&obj)?;
-    let (car2, _) = destructure_list(&cdr)?;
-    if integer_value(&car1)? < integer_value(&car2)? {
-        Object::Integer(1).into()
-    } else {
-        result_nil()
-    }
+    chained(&obj, i32::lt)
+}
+
+pub fn greater_or_equal(obj: RefObject) -> ResultRefObject {
+    chained(&obj, i32::ge)
+}
+
+pub fn less_or_equal(obj: RefObject) -> ResultRefObject {
+    chained(&obj, i32::le)
 }
 
 pub fn equal_to(obj: RefObject) -> ResultRefObject {
-    let (car1, cdr) = destructure_list(&obj)?;
-    let (car2, _) = destructure_list(&cdr)?;
-    if integer_value(&car1)? == integer_value(&car2)? {
-        Object::Integer(1).into()
-    } else {
+    chained(&obj, i32::eq)
+}
+
+/// True when no two values are equal, not only adjacent ones.
+fn pairwise_distinct<T>(values: &[T], equal: fn(&T, &T) -> bool) -> ResultRefObject {
+    if values.is_empty() {
+        return Err(Error::WrongArgumentCount);
+    }
+    boolean(
+        values
+            .iter()
+            .enumerate()
+            .all(|(i, a)| values[i + 1..].iter().all(|b| !equal(a, b))),
+    )
+}
+
+pub fn not_equal_to(obj: RefObject) -> ResultRefObject {
+    pairwise_distinct(&integer_arguments(&obj)?, i32::eq)
+}
+
+pub fn cons(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [car, cdr] => Object::Cons(Arc::clone(car), Arc::clone(cdr)).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn list_of(obj: RefObject) -> ResultRefObject {
+    Ok(obj)
+}
+
+/// Copies every list but the last, which becomes the shared tail.
+pub fn append(obj: RefObject) -> ResultRefObject {
+    let mut lists = arguments(&obj)?;
+    let mut result = lists.pop().unwrap_or_else(nil);
+    while let Some(next) = lists.pop() {
+        result = list_with_tail(list_elements(&next)?, result);
+    }
+    Ok(result)
+}
+
+pub fn reverse(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [l] => Ok(list(list_elements(l)?.into_iter().rev().collect::<Vec<_>>())),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn length(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [l] => {
+            let length = list_elements(l)?.len();
+            Object::Integer(i32::try_from(length).map_err(|_| Error::Overflow)?).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+fn drop_elements(n: usize, l: &RefObject) -> ResultRefObject {
+    let mut next = l;
+    for _ in 0..n {
+        if !not_nil(next) {
+            break;
+        }
+        let (_, cdr) = destructure_list(next).map_err(|_| Error::NotProperList)?;
+        next = cdr;
+    }
+    Ok(Arc::clone(next))
+}
+
+pub fn nthcdr(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [n, l] => drop_elements(index_value(n)?, l),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn nth(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [n, l] => {
+            let tail = drop_elements(index_value(n)?, l)?;
+            if not_nil(&tail) {
+                let (car, _) = destructure_list(&tail).map_err(|_| Error::NotProperList)?;
+                Ok(Arc::clone(car))
+            } else {
+                result_nil()
+            }
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Returns the last cons of the list, or nil for the empty list. The last
+/// cons of a dotted list holds its final cdr, so `(LAST '(1 2 . 3))` is `(2 . 3)`.
+pub fn last(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [l] => {
+            let mut next = l;
+            while let Some(Object::Cons(_, cdr)) = next.as_ref() {
+                if let Some(Object::Cons(_, _)) = cdr.as_ref() {
+                    next = cdr;
+                } else {
+                    return Ok(Arc::clone(next));
+                }
+            }
+            if not_nil(next) {
+                Err(Error::NotProperList)
+            } else {
+                result_nil()
+            }
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Returns the tail of the list starting at the first element `eql` to the item.
+pub fn member(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [item, l] => {
+            let mut next = l;
+            while not_nil(next) {
+                let (car, cdr) = destructure_list(next).map_err(|_| Error::NotProperList)?;
+                if eql(item, car) {
+                    return Ok(Arc::clone(next));
+                }
+                next = cdr;
+            }
+            result_nil()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Returns the first pair of the association list whose key is `eql` to the item.
+pub fn assoc(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [key, alist] => {
+            for pair in list_elements(alist)? {
+                if not_nil(&pair) {
+                    let (car, _) = destructure_list(&pair)?;
+                    if eql(key, car) {
+                        return Ok(pair);
+                    }
+                }
+            }
+            result_nil()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Copies the conses of the list, keeping the final cdr of a dotted list.
+pub fn copy_list(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [l] => {
+            let (elements, tail) = list_parts(l);
+            Ok(list_with_tail(elements, tail))
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Splits `(function list ...)` arguments, requiring at least one list.
+fn function_and_lists(obj: &RefObject) -> errors::Result<(RefObject, Vec<Vec<RefObject>>)> {
+    let mut args = arguments(obj)?;
+    if args.len() < 2 {
+        return Err(Error::WrongArgumentCount);
+    }
+    let function = args.remove(0);
+    let lists = args
+        .iter()
+        .map(list_elements)
+        .collect::<errors::Result<Vec<_>>>()?;
+    Ok((function, lists))
+}
+
+/// Argument lists taking one element from each list, up to the shortest one.
+fn zip_lists(lists: &[Vec<RefObject>]) -> Vec<RefObject> {
+    let count = lists.iter().map(Vec::len).min().unwrap_or(0);
+    (0..count)
+        .map(|i| list(lists.iter().map(|l| Arc::clone(&l[i]))))
+        .collect()
+}
+
+/// Applies the function to the elements of the lists in parallel.
+pub fn mapcar(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let (function, lists) = function_and_lists(&obj)?;
+        let values = parallel_apply(&function, zip_lists(&lists), context).await?;
+        Ok(list(values))
+    }
+    .boxed()
+}
+
+pub fn filter(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        match function_and_lists(&obj)? {
+            (predicate, lists) if lists.len() == 1 => {
+                let elements = &lists[0];
+                let tests = parallel_apply(&predicate, zip_lists(&lists), context).await?;
+                Ok(list(
+                    elements
+                        .iter()
+                        .zip(tests)
+                        .filter(|(_, test)| not_nil(test))
+                        .map(|(element, _)| Arc::clone(element))
+                        .collect::<Vec<_>>(),
+                ))
+            }
+            _ => Err(Error::WrongArgumentCount),
+        }
+    }
+    .boxed()
+}
+
+/// `(reduce function list [initial])`, folding from the left.
+pub fn reduce(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let (function, elements, initial) = match arguments(&obj)?.as_slice() {
+            [function, l] => (Arc::clone(function), list_elements(l)?, None),
+            [function, l, initial] => (
+                Arc::clone(function),
+                list_elements(l)?,
+                Some(Arc::clone(initial)),
+            ),
+            _ => return Err(Error::WrongArgumentCount),
+        };
+        let mut elements = elements.into_iter();
+        let mut total = match initial.or_else(|| elements.next()) {
+            Some(value) => value,
+            None => return apply(function, nil(), context).await,
+        };
+        for element in elements {
+            total = apply(Arc::clone(&function), list(vec![total, element]), context.clone()).await?;
+        }
+        Ok(total)
+    }
+    .boxed()
+}
+
+/// Applies the function to the elements of the lists in order, for side effects.
+pub fn for_each(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let (function, lists) = function_and_lists(&obj)?;
+        for arguments in zip_lists(&lists) {
+            apply(Arc::clone(&function), arguments, context.clone()).await?;
+        }
         result_nil()
     }
+    .boxed()
+}
+
+/// `(apply function arg ... list)`: the last argument supplies the rest.
+pub fn apply_operator(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let mut args = arguments(&obj)?;
+        if args.len() < 2 {
+            return Err(Error::WrongArgumentCount);
+        }
+        let function = args.remove(0);
+        let rest = args.pop().unwrap();
+        list_elements(&rest)?;
+        apply(function, list_with_tail(args, rest), context).await
+    }
+    .boxed()
+}
+
+pub fn funcall(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let (function, cdr) = destructure_list(&obj).map_err(|_| Error::WrongArgumentCount)?;
+        apply(Arc::clone(function), Arc::clone(cdr), context).await
+    }
+    .boxed()
+}
+
+async fn merge(
+    left: Vec<RefObject>,
+    right: Vec<RefObject>,
+    predicate: &RefObject,
+    context: &Context,
+) -> errors::Result<Vec<RefObject>> {
+    let mut result = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let arguments = list(vec![Arc::clone(r), Arc::clone(l)]);
+        if not_nil(&apply(Arc::clone(predicate), arguments, context.clone()).await?) {
+            result.extend(right.next());
+        } else {
+            result.extend(left.next());
+        }
+    }
+    result.extend(left);
+    result.extend(right);
+    Ok(result)
+}
+
+/// `(sort list predicate)`: a stable merge sort returning a new list.
+pub fn sort(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let (elements, predicate) = match arguments(&obj)?.as_slice() {
+            [l, predicate] => (list_elements(l)?, Arc::clone(predicate)),
+            _ => return Err(Error::WrongArgumentCount),
+        };
+        let mut runs: Vec<Vec<RefObject>> = elements.into_iter().map(|e| vec![e]).collect();
+        while runs.len() > 1 {
+            let mut merged = Vec::with_capacity(runs.len() / 2 + 1);
+            let mut pending = runs.into_iter();
+            while let Some(left) = pending.next() {
+                match pending.next() {
+                    Some(right) => merged.push(merge(left, right, &predicate, &context).await?),
+                    None => merged.push(left),
+                }
+            }
+            runs = merged;
+        }
+        Ok(list(runs.pop().unwrap_or_default()))
+    }
+    .boxed()
+}
+
+pub fn eval_operator(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        match arguments(&obj)?.as_slice() {
+            [form] => context.eval(form).await,
+            _ => Err(Error::WrongArgumentCount),
+        }
+    }
+    .boxed()
+}
+
+/// Writes each argument on its own line to the context output and returns the last one.
+pub fn print(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let values = arguments(&obj)?;
+        let nil_name = context.environment.case().builtin("NIL");
+        let mut output = context.output.lock().unwrap();
+        for value in &values {
+            match value.as_ref() {
+                Some(v) => writeln!(output, "{}", v)?,
+                None => writeln!(output, "{}", nil_name)?,
+            }
+        }
+        output.flush()?;
+        Ok(values.last().cloned().unwrap_or_else(nil))
+    }
+    .boxed()
+}
+
+/// `(sleep milliseconds)` suspends the calling task without blocking the runtime.
+pub fn sleep(obj: RefObject, _context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        match arguments(&obj)?.as_slice() {
+            [milliseconds] => {
+                let milliseconds =
+                    u64::try_from(integer_value(milliseconds)?).map_err(|_| Error::OutOfDomain)?;
+                tokio::time::delay_for(Duration::from_millis(milliseconds)).await;
+                result_nil()
+            }
+            _ => Err(Error::WrongArgumentCount),
+        }
+    }
+    .boxed()
+}
+
+/// The character's Unicode scalar value.
+pub fn char_to_integer(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [ch] => Object::Integer(char_value(ch)? as i32).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn integer_to_char(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [code] => {
+            let ch = u32::try_from(*code)
+                .ok()
+                .and_then(std::char::from_u32)
+                .ok_or(Error::OutOfDomain)?;
+            Object::Char(ch).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Characters whose case mapping is more than one character are left alone.
+fn single_char(mut mapped: impl Iterator<Item = char>, ch: char) -> ResultRefObject {
+    match (mapped.next(), mapped.next()) {
+        (Some(mapped), None) => Object::Char(mapped).into(),
+        _ => Object::Char(ch).into(),
+    }
+}
+
+pub fn char_upcase(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [ch] => {
+            let ch = char_value(ch)?;
+            single_char(ch.to_uppercase(), ch)
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn char_downcase(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [ch] => {
+            let ch = char_value(ch)?;
+            single_char(ch.to_lowercase(), ch)
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn char_alphabetic(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [ch] => boolean(char_value(ch)?.is_alphabetic()),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// `(CHAR string index)`, indexing by character rather than by byte.
+pub fn char_at(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [string, index] => {
+            let ch = string_value(string)?
+                .chars()
+                .nth(index_value(index)?)
+                .ok_or(Error::OutOfDomain)?;
+            Object::Char(ch).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn vector_of(obj: RefObject) -> ResultRefObject {
+    Ok(vector(arguments(&obj)?))
+}
+
+/// The longest vector `MAKE-VECTOR` allocates.
+const MAX_VECTOR_LENGTH: usize = 1 << 24;
+
+/// `(MAKE-VECTOR length [fill])`, filled with nil by default.
+pub fn make_vector(obj: RefObject) -> ResultRefObject {
+    let (length, fill) = match arguments(&obj)?.as_slice() {
+        [length] => (index_value(length)?, nil()),
+        [length, fill] => (index_value(length)?, Arc::clone(fill)),
+        _ => return Err(Error::WrongArgumentCount),
+    };
+    if length > MAX_VECTOR_LENGTH {
+        return Err(Error::OutOfDomain);
+    }
+    Ok(vector(vec![fill; length]))
+}
+
+pub fn vector_ref(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [v, index] => {
+            let elements = vector_value(v)?.read().unwrap();
+            elements
+                .get(index_value(index)?)
+                .cloned()
+                .ok_or(Error::OutOfDomain)
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Held across the cycle check and the store of `vector_set`, so that two
+/// concurrent stores can't each pass the check and link two vectors together.
+static VECTOR_STORES: Mutex<()> = Mutex::new(());
+
+/// Replaces the element in place, so every reference to the vector sees it.
+/// A value that contains the vector would make it print and compare forever,
+/// so it is `Error::Cycle`.
+pub fn vector_set(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [v, index, value] => {
+            let _store = VECTOR_STORES.lock().unwrap();
+            if refers_to(value, v) {
+                return Err(Error::Cycle);
+            }
+            let mut elements = vector_value(v)?.write().unwrap();
+            let element = elements
+                .get_mut(index_value(index)?)
+                .ok_or(Error::OutOfDomain)?;
+            *element = Arc::clone(value);
+            Ok(Arc::clone(value))
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn vector_length(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [v] => {
+            let length = vector_value(v)?.read().unwrap().len();
+            Object::Integer(i32::try_from(length).map_err(|_| Error::Overflow)?).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn vector_to_list(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [v] => Ok(list(vector_value(v)?.read().unwrap().clone())),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn list_to_vector(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [l] => Ok(vector(list_elements(l)?)),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Applies the function to the elements of the vectors in parallel, into a
+/// new vector.
+pub fn vector_map(obj: RefObject, context: Context) -> BoxFuture<'static, ResultRefObject> {
+    async move {
+        let mut args = arguments(&obj)?;
+        if args.len() < 2 {
+            return Err(Error::WrongArgumentCount);
+        }
+        let function = args.remove(0);
+        let vectors = args
+            .iter()
+            .map(|v| Ok(vector_value(v)?.read().unwrap().clone()))
+            .collect::<errors::Result<Vec<_>>>()?;
+        let values = parallel_apply(&function, zip_lists(&vectors), context).await?;
+        Ok(vector(values))
+    }
+    .boxed()
+}
+
+/// `(GET map key [default])`, the default being nil.
+pub fn get(obj: RefObject) -> ResultRefObject {
+    let (map, key, default) = match arguments(&obj)?.as_slice() {
+        [map, key] => (Arc::clone(map), Arc::clone(key), nil()),
+        [map, key, default] => (Arc::clone(map), Arc::clone(key), Arc::clone(default)),
+        _ => return Err(Error::WrongArgumentCount),
+    };
+    Ok(map_value(&map)?.get(&key).cloned().unwrap_or(default))
+}
+
+/// `(PUT map key value ...)`, a copy of the map with the entries added.
+pub fn put(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [map, entries @ ..] if !entries.is_empty() && entries.len() % 2 == 0 => {
+            let mut map = map_value(map)?.clone();
+            for pair in entries.chunks(2) {
+                map.insert(map_key(&pair[0])?, Arc::clone(&pair[1]));
+            }
+            Object::Map(map).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn dissoc(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [map, keys @ ..] => {
+            let mut map = map_value(map)?.clone();
+            for key in keys {
+                map.remove(key);
+            }
+            Object::Map(map).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn keys(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [map] => Ok(list(map_value(map)?.keys().cloned().collect::<Vec<_>>())),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// The values in the same order as `KEYS`.
+pub fn vals(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [map] => Ok(list(map_value(map)?.values().cloned().collect::<Vec<_>>())),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn contains(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [map, key] => boolean(map_value(map)?.contains_key(key)),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Entries of later maps replace those of earlier ones.
+pub fn merge_maps(obj: RefObject) -> ResultRefObject {
+    let mut merged = Map::default();
+    for map in arguments(&obj)? {
+        for (key, value) in map_value(&map)? {
+            merged.insert(Arc::clone(key), Arc::clone(value));
+        }
+    }
+    Object::Map(merged).into()
+}
+
+fn string_arguments(obj: &RefObject) -> errors::Result<Vec<String>> {
+    arguments(obj)?.iter().map(string_value).collect()
+}
+
+/// Byte offset of the character at `index`, which may be one past the end.
+fn char_offset(string: &str, index: usize) -> errors::Result<usize> {
+    string
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(string.len()))
+        .nth(index)
+        .ok_or(Error::OutOfDomain)
+}
+
+pub fn string_length(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => {
+            let length = i32::try_from(string.chars().count()).map_err(|_| Error::Overflow)?;
+            Object::Integer(length).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// `(SUBSTRING string start [end])` with character indexes, `end` excluded.
+pub fn substring(obj: RefObject) -> ResultRefObject {
+    let (string, start, end) = match arguments(&obj)?.as_slice() {
+        [string, start] => (string_value(string)?, index_value(start)?, None),
+        [string, start, end] => (
+            string_value(string)?,
+            index_value(start)?,
+            Some(index_value(end)?),
+        ),
+        _ => return Err(Error::WrongArgumentCount),
+    };
+    let from = char_offset(&string, start)?;
+    let to = match end {
+        Some(end) if end < start => return Err(Error::OutOfDomain),
+        Some(end) => char_offset(&string, end)?,
+        None => string.len(),
+    };
+    Object::IString(string[from..to].to_string()).into()
+}
+
+pub fn string_append(obj: RefObject) -> ResultRefObject {
+    Object::IString(string_arguments(&obj)?.concat()).into()
+}
+
+pub fn string_upcase(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => Object::IString(string.to_uppercase()).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn string_downcase(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => Object::IString(string.to_lowercase()).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Splits on `separator`, or on runs of whitespace when there is none.
+pub fn string_split(obj: RefObject) -> ResultRefObject {
+    let strings = string_arguments(&obj)?;
+    let parts: Vec<&str> = match strings.as_slice() {
+        [string] => string.split_whitespace().collect(),
+        [_, separator] if separator.is_empty() => return Err(Error::OutOfDomain),
+        [string, separator] => string.split(separator.as_str()).collect(),
+        _ => return Err(Error::WrongArgumentCount),
+    };
+    Ok(list(
+        parts
+            .into_iter()
+            .map(|part| Object::IString(part.to_string()).into()),
+    ))
+}
+
+/// `(STRING-JOIN list [separator])`
+pub fn string_join(obj: RefObject) -> ResultRefObject {
+    let (strings, separator) = match arguments(&obj)?.as_slice() {
+        [strings] => (strings.clone(), String::new()),
+        [strings, separator] => (strings.clone(), string_value(separator)?),
+        _ => return Err(Error::WrongArgumentCount),
+    };
+    let strings = string_arguments(&strings)?;
+    Object::IString(strings.join(&separator)).into()
+}
+
+pub fn string_trim(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => Object::IString(string.trim().to_string()).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// The character index of the first occurrence of `part`, or nil.
+pub fn string_contains(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string, part] => match string.find(part.as_str()) {
+            Some(offset) => {
+                let index = i32::try_from(string[..offset].chars().count())
+                    .map_err(|_| Error::Overflow)?;
+                Object::Integer(index).into()
+            }
+            None => result_nil(),
+        },
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Nil when the string isn't an integer.
+pub fn string_to_number(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => match string.trim().parse::<i32>() {
+            Ok(value) => Object::Integer(value).into(),
+            Err(_) => result_nil(),
+        },
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn number_to_string(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [value] => Object::IString(value.to_string()).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn string_to_symbol(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [string] => Object::Symbol(Symbol::intern(string)).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn symbol_to_string(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [symbol] => Object::IString(symbol_value(symbol)?.to_string()).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+fn string_chained(obj: &RefObject, test: fn(&String, &String) -> bool) -> ResultRefObject {
+    let values = string_arguments(obj)?;
+    if values.is_empty() {
+        return Err(Error::WrongArgumentCount);
+    }
+    boolean(values.windows(2).all(|pair| test(&pair[0], &pair[1])))
+}
+
+pub fn string_equal_to(obj: RefObject) -> ResultRefObject {
+    string_chained(&obj, String::eq)
+}
+
+pub fn string_less_than(obj: RefObject) -> ResultRefObject {
+    string_chained(&obj, String::lt)
+}
+
+pub fn string_greater_than(obj: RefObject) -> ResultRefObject {
+    string_chained(&obj, String::gt)
+}
+
+pub fn string_less_or_equal(obj: RefObject) -> ResultRefObject {
+    string_chained(&obj, String::le)
+}
+
+pub fn string_greater_or_equal(obj: RefObject) -> ResultRefObject {
+    string_chained(&obj, String::ge)
+}
+
+pub fn string_not_equal_to(obj: RefObject) -> ResultRefObject {
+    pairwise_distinct(&string_arguments(&obj)?, String::eq)
+}
+
+/// `(GENSYM [prefix])`, a new symbol unequal to any other.
+pub fn gensym(obj: RefObject) -> ResultRefObject {
+    match string_arguments(&obj)?.as_slice() {
+        [] => Object::Symbol(Symbol::gensym("G")).into(),
+        [prefix] => Object::Symbol(Symbol::gensym(prefix)).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn keywordp(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [value] => boolean(matches!(value.as_ref(), Some(Object::Keyword(_)))),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn consp(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [value] => boolean(matches!(value.as_ref(), Some(Object::Cons(_, _)))),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn atom(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [value] => boolean(!matches!(value.as_ref(), Some(Object::Cons(_, _)))),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// `(foreignp obj [type-name])`: whether the object is a host value, optionally
+/// of the type named by its full path or last path segment.
+pub fn foreignp(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [value] => boolean(matches!(value.as_ref(), Some(Object::Foreign(_, _)))),
+        [value, name] => {
+            let name = string_value(name)?;
+            match value.as_ref() {
+                Some(Object::Foreign(type_name, _)) => {
+                    boolean(*type_name == name || type_name.rsplit("::").next() == Some(&name))
+                }
+                _ => result_nil(),
+            }
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// `(exit [status])` unwinds the evaluation with `Error::Exit`, leaving it to
+/// the host to decide what ending the program means.
+pub fn exit(obj: RefObject) -> ResultRefObject {
+    match arguments(&obj)?.as_slice() {
+        [] => Err(Error::Exit(0)),
+        [status] => Err(Error::Exit(integer_value(status)?)),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn eq_predicate(obj: RefObject) -> ResultRefObject {
+    let (car1, cdr) = destructure_list(&obj)?;
+    let (car2, _) = destructure_list(cdr)?;
+    boolean(eq(car1, car2))
+}
+
+pub fn eql_predicate(obj: RefObject) -> ResultRefObject {
+    let (car1, cdr) = destructure_list(&obj)?;
+    let (car2, _) = destructure_list(cdr)?;
+    boolean(eql(car1, car2))
+}
+
+pub fn equal_predicate(obj: RefObject) -> ResultRefObject {
+    let (car1, cdr) = destructure_list(&obj)?;
+    let (car2, _) = destructure_list(cdr)?;
+    boolean(equal(car1, car2))
 }
 
 pub fn quote(obj: RefObject) -> ResultRefObject {
@@ -150,12 +1047,181 @@ pub fn quote(obj: RefObject) -> ResultRefObject {
     Ok(Arc::clone(car))
 }
 
+fn gcd_of(a: i32, b: i32) -> errors::Result<i32> {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    i32::try_from(a).map_err(|_| Error::Overflow)
+}
+
+/// Floored modulo: the result takes the sign of the divisor.
+pub fn modulo(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [_, 0] => Err(Error::DivisionByZero),
+        [a, b] => {
+            // Unlike the quotient, `MIN % -1` is 0, so it can only wrap to 0.
+            let r = a.wrapping_rem(*b);
+            if r != 0 && (r < 0) != (*b < 0) {
+                Object::Integer(r + b).into()
+            } else {
+                Object::Integer(r).into()
+            }
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Truncated remainder: the result takes the sign of the dividend.
+pub fn rem(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [_, 0] => Err(Error::DivisionByZero),
+        [a, b] => Object::Integer(a.wrapping_rem(*b)).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn abs(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [a] => Object::Integer(a.checked_abs().ok_or(Error::Overflow)?).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn min(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.into_iter().min() {
+        Some(value) => Object::Integer(value).into(),
+        None => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn max(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.into_iter().max() {
+        Some(value) => Object::Integer(value).into(),
+        None => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn expt(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [base, power] => {
+            let power = u32::try_from(*power).map_err(|_| Error::OutOfDomain)?;
+            Object::Integer(base.checked_pow(power).ok_or(Error::Overflow)?).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Integer square root, rounded down.
+pub fn sqrt(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [a] if *a < 0 => Err(Error::OutOfDomain),
+        [a] => {
+            let mut root = (*a as f64).sqrt() as i64;
+            while root * root > *a as i64 {
+                root -= 1;
+            }
+            while (root + 1) * (root + 1) <= *a as i64 {
+                root += 1;
+            }
+            Object::Integer(root as i32).into()
+        }
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn gcd(obj: RefObject) -> ResultRefObject {
+    let mut total = 0i32;
+    for value in integer_arguments(&obj)? {
+        total = gcd_of(total, value)?;
+    }
+    Object::Integer(total).into()
+}
+
+pub fn lcm(obj: RefObject) -> ResultRefObject {
+    let mut total = 1i32;
+    for value in integer_arguments(&obj)? {
+        if value == 0 {
+            total = 0;
+        } else if total != 0 {
+            total = (total / gcd_of(total, value)?)
+                .checked_mul(value)
+                .and_then(i32::checked_abs)
+                .ok_or(Error::Overflow)?;
+        }
+    }
+    Object::Integer(total).into()
+}
+
+pub fn zerop(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [a] => boolean(*a == 0),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn evenp(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [a] => boolean(a % 2 == 0),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn oddp(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [a] => boolean(a % 2 != 0),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+pub fn logand(obj: RefObject) -> ResultRefObject {
+    Object::Integer(integer_arguments(&obj)?.into_iter().fold(-1, |a, b| a & b)).into()
+}
+
+pub fn logior(obj: RefObject) -> ResultRefObject {
+    Object::Integer(integer_arguments(&obj)?.into_iter().fold(0, |a, b| a | b)).into()
+}
+
+pub fn logxor(obj: RefObject) -> ResultRefObject {
+    Object::Integer(integer_arguments(&obj)?.into_iter().fold(0, |a, b| a ^ b)).into()
+}
+
+/// Arithmetic shift: left for a positive count, right for a negative one.
+pub fn ash(obj: RefObject) -> ResultRefObject {
+    match integer_arguments(&obj)?.as_slice() {
+        [0, _] => Object::Integer(0).into(),
+        [a, count] if *count >= 0 => {
+            let shifted = (*a as i64)
+                .checked_shl(*count as u32)
+                .filter(|v| v >> *count as u32 == *a as i64)
+                .and_then(|v| i32::try_from(v).ok())
+                .ok_or(Error::Overflow)?;
+            Object::Integer(shifted).into()
+        }
+        [a, count] => Object::Integer(a >> count.unsigned_abs().min(31)).into(),
+        _ => Err(Error::WrongArgumentCount),
+    }
+}
+
+/// Binds the builtins, named in upper or lower case to match the
+/// environment's `Case`.
 pub fn initialize_operators(environment: &RefEnvironment) {
+    let case = environment.case();
     macro_rules! register {
         ($name:literal, $func:ident) => {
+            let symbol = case.builtin($name);
+            environment.0.write().unwrap().intern(
+                symbol,
+                Arc::new(Some(Object::Operator(symbol.to_string(), $func))),
+            );
+        };
+        ($name:literal, async $func:ident) => {
+            let symbol = case.builtin($name);
             environment.0.write().unwrap().intern(
-                String::from($name),
-                Arc::new(Some(Object::Operator(String::from($name), $func))),
+                symbol,
+                Arc::new(Some(Object::AsyncOperator(symbol.to_string(), $func))),
             );
         };
     }
@@ -165,6 +1231,27 @@ pub fn initialize_operators(environment: &RefEnvironment) {
     register!("NOT", not);
     register!("CAR", car);
     register!("CDR", cdr);
+    register!("CONS", cons);
+    register!("LIST", list_of);
+    register!("APPEND", append);
+    register!("REVERSE", reverse);
+    register!("LENGTH", length);
+    register!("NTH", nth);
+    register!("NTHCDR", nthcdr);
+    register!("LAST", last);
+    register!("MEMBER", member);
+    register!("ASSOC", assoc);
+    register!("COPY-LIST", copy_list);
+    register!("MAPCAR", async mapcar);
+    register!("FILTER", async filter);
+    register!("REDUCE", async reduce);
+    register!("FOR-EACH", async for_each);
+    register!("APPLY", async apply_operator);
+    register!("FUNCALL", async funcall);
+    register!("SORT", async sort);
+    register!("EVAL", async eval_operator);
+    register!("PRINT", async print);
+    register!("SLEEP", async sleep);
     register!("+", sum);
     register!("-", sub);
     register!("*", mult);
@@ -172,4 +1259,73 @@ pub fn initialize_operators(environment: &RefEnvironment) {
     register!("=", equal_to);
     register!("<", less_than);
     register!(">", greater_than);
+    register!("<=", less_or_equal);
+    register!(">=", greater_or_equal);
+    register!("/=", not_equal_to);
+    register!("EXIT", exit);
+    register!("FOREIGNP", foreignp);
+    register!("CONSP", consp);
+    register!("KEYWORDP", keywordp);
+    register!("ATOM", atom);
+    register!("EQ", eq_predicate);
+    register!("EQL", eql_predicate);
+    register!("EQUAL", equal_predicate);
+    register!("MOD", modulo);
+    register!("REM", rem);
+    register!("ABS", abs);
+    register!("MIN", min);
+    register!("MAX", max);
+    register!("EXPT", expt);
+    register!("SQRT", sqrt);
+    register!("GCD", gcd);
+    register!("LCM", lcm);
+    register!("ZEROP", zerop);
+    register!("EVENP", evenp);
+    register!("ODDP", oddp);
+    register!("LOGAND", logand);
+    register!("LOGIOR", logior);
+    register!("LOGXOR", logxor);
+    register!("ASH", ash);
+    register!("CHAR->INTEGER", char_to_integer);
+    register!("INTEGER->CHAR", integer_to_char);
+    register!("CHAR-UPCASE", char_upcase);
+    register!("CHAR-DOWNCASE", char_downcase);
+    register!("CHAR-ALPHABETIC?", char_alphabetic);
+    register!("CHAR", char_at);
+    register!("VECTOR", vector_of);
+    register!("MAKE-VECTOR", make_vector);
+    register!("VECTOR-REF", vector_ref);
+    register!("VECTOR-SET!", vector_set);
+    register!("VECTOR-LENGTH", vector_length);
+    register!("VECTOR->LIST", vector_to_list);
+    register!("LIST->VECTOR", list_to_vector);
+    register!("VECTOR-MAP", async vector_map);
+    register!("GET", get);
+    register!("PUT", put);
+    register!("DISSOC", dissoc);
+    register!("KEYS", keys);
+    register!("VALS", vals);
+    register!("CONTAINS?", contains);
+    register!("MERGE", merge_maps);
+    register!("STRING-LENGTH", string_length);
+    register!("SUBSTRING", substring);
+    register!("STRING-APPEND", string_append);
+    register!("STRING-UPCASE", string_upcase);
+    register!("STRING-DOWNCASE", string_downcase);
+    register!("STRING-SPLIT", string_split);
+    register!("STRING-JOIN", string_join);
+    register!("STRING-TRIM", string_trim);
+    register!("STRING-CONTAINS", string_contains);
+    register!("STRING->NUMBER", string_to_number);
+    register!("NUMBER->STRING", number_to_string);
+    register!("STRING->SYMBOL", string_to_symbol);
+    register!("INTERN", string_to_symbol);
+    register!("GENSYM", gensym);
+    register!("SYMBOL->STRING", symbol_to_string);
+    register!("STRING=", string_equal_to);
+    register!("STRING<", string_less_than);
+    register!("STRING>", string_greater_than);
+    register!("STRING<=", string_less_or_equal);
+    register!("STRING>=", string_greater_or_equal);
+    register!("STRING/=", string_not_equal_to);
 }
@@ -0,0 +1,177 @@
+use crate::environment::RefEnvironment;
+use crate::errors::{self, Error};
+use crate::evaluator::{self, operators, Context, Output};
+use crate::object::{nil, FromLustre, IntoLustre, RefObject, ResultRefObject};
+use crate::reader::{tokenizer::Tokenizer, Reader};
+use crate::symbol::Case;
+
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::path::Path;
+use tokio::runtime::Runtime;
+
+/// An interpreter owning its runtime and a global environment with the
+/// builtin operators, for applications embedding Lustre.
+pub struct Interpreter {
+    runtime: Runtime,
+    context: Context,
+}
+
+impl Interpreter {
+    pub fn new() -> errors::Result<Self> {
+        Self::with_case(Case::default())
+    }
+
+    /// An interpreter reading identifiers, naming builtins and resolving the
+    /// names given to `define`, `get` and `call` as `case` does.
+    pub fn with_case(case: Case) -> errors::Result<Self> {
+        let runtime = Runtime::new()?;
+        let environment = RefEnvironment::with_case(case);
+        operators::initialize_operators(&environment);
+        let context = Context::new(environment, runtime.handle().clone());
+        Ok(Self { runtime, context })
+    }
+
+    /// Sends the output of `PRINT` and friends to `output` instead of stdout.
+    pub fn with_output(self, output: Output) -> Self {
+        Self {
+            context: self.context.with_output(output),
+            ..self
+        }
+    }
+
+    pub fn environment(&self) -> &RefEnvironment {
+        &self.context.environment
+    }
+
+    /// Evaluates a form already read with a `Reader`.
+    pub fn eval(&mut self, obj: &RefObject) -> ResultRefObject {
+        evaluator::eval_with_context(obj, self.context.clone(), &mut self.runtime)
+    }
+
+    /// Evaluates every form in `code`, returning the value of the last one.
+    pub fn eval_str(&mut self, code: &str) -> ResultRefObject {
+        self.eval_reader(Cursor::new(code))
+    }
+
+    /// Evaluates a source file. A leading `#!` line is ignored so scripts can
+    /// be made executable.
+    pub fn eval_file<P: AsRef<Path>>(&mut self, path: P) -> ResultRefObject {
+        let mut source = String::new();
+        File::open(path)?.read_to_string(&mut source)?;
+        if source.starts_with("#!") {
+            let end = source.find('\n').unwrap_or(source.len());
+            source.replace_range(..end, "");
+        }
+        self.eval_str(&source)
+    }
+
+    pub fn eval_reader<R: Read>(&mut self, input: R) -> ResultRefObject {
+        let reader = Reader::new(Tokenizer::new(BufReader::new(input).bytes()))
+            .with_case(self.environment().case());
+        let mut result = nil();
+        for ast in reader {
+            result = self.eval(&ast?)?;
+        }
+        Ok(result)
+    }
+
+    /// Calls the function bound to `name` with `arguments`, anything converting
+    /// to a list such as a tuple or a `Vec`.
+    pub fn call<A: IntoLustre>(&mut self, name: &str, arguments: A) -> ResultRefObject {
+        let function = self.lookup(name)?;
+        let arguments = arguments.into_lustre_with_case(self.environment().case())?;
+        let context = self.context.clone();
+        self.runtime
+            .block_on(async move { context.apply(function, arguments).await })
+    }
+
+    /// Binds `name` in the global environment, replacing any previous value.
+    pub fn define<V: IntoLustre>(&mut self, name: &str, value: V) -> ResultRefObject {
+        let value = value.into_lustre_with_case(self.environment().case())?;
+        let symbol = self.environment().case().symbol(name);
+        Ok(self
+            .context
+            .environment
+            .0
+            .write()
+            .unwrap()
+            .intern(symbol, value))
+    }
+
+    pub fn get<T: FromLustre>(&self, name: &str) -> errors::Result<T> {
+        T::from_lustre_with_case(&self.lookup(name)?, self.environment().case())
+    }
+
+    fn lookup(&self, name: &str) -> ResultRefObject {
+        let symbol = self.environment().case().symbol(name);
+        self.context
+            .environment
+            .0
+            .read()
+            .unwrap()
+            .find_symbol(symbol)
+            .ok_or_else(|| Error::Unbound(symbol.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpreter_test() {
+        let mut interpreter = Interpreter::new().unwrap();
+        interpreter.define("base", 10).unwrap();
+        interpreter
+            .eval_str("(def 'scale (lambda (x y) (* base (+ x y)))) (def 'answer (scale 1 2))")
+            .unwrap();
+        assert_eq!(30, interpreter.get::<i32>("answer").unwrap());
+        let result = interpreter.call("scale", (3, 4)).unwrap();
+        assert_eq!(70, i32::from_lustre(&result).unwrap());
+        assert!(matches!(interpreter.get::<i32>("missing"), Err(Error::Unbound(_))));
+        assert!(matches!(interpreter.eval_str("(+ 1 missing)"), Err(Error::Unbound(_))));
+    }
+
+    #[test]
+    fn shebang_and_exit_test() {
+        let path = std::env::temp_dir().join(format!("lustre-{}.lustre", std::process::id()));
+        std::fs::write(&path, "#!/usr/bin/env lustre\n(exit (length *args*))\n").unwrap();
+        let mut interpreter = Interpreter::new().unwrap();
+        interpreter
+            .define("*ARGS*", vec![String::from("a"), String::from("b")])
+            .unwrap();
+        let result = interpreter.eval_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(Error::Exit(2))));
+    }
+
+    #[test]
+    fn eval_file_test() {
+        let mut interpreter = Interpreter::new().unwrap();
+        let result = interpreter.eval_file("test.lustre").unwrap();
+        assert_eq!(13 * 5040, i32::from_lustre(&result).unwrap());
+    }
+
+    #[test]
+    fn case_test() {
+        let mut interpreter = Interpreter::with_case(Case::Preserve).unwrap();
+        interpreter.define("maxItems", 3).unwrap();
+        interpreter
+            .eval_str("(def 'Foo 1) (def 'foo 2) (def 'sum (+ maxItems Foo foo))")
+            .unwrap();
+        assert_eq!(6, interpreter.get::<i32>("sum").unwrap());
+        assert!(matches!(interpreter.get::<i32>("SUM"), Err(Error::Unbound(_))));
+        let quoted = interpreter.eval_str("(if nil 0 (car '('Mixed :Key)))").unwrap();
+        assert_eq!("( quote Mixed )", format!("{}", quoted.as_ref().as_ref().unwrap()));
+        assert!(matches!(interpreter.eval_str("(CAR '(1))"), Err(Error::Unbound(_))));
+        assert!(matches!(interpreter.eval_str("(IF nil 1 2)"), Err(Error::Unbound(_))));
+
+        let mut interpreter = Interpreter::new().unwrap();
+        interpreter.define("maxItems", 3).unwrap();
+        assert_eq!(3, interpreter.get::<i32>("MAXITEMS").unwrap());
+        assert!(matches!(interpreter.eval_str("(def 'Foo 1) (def 'foo 2)"), Err(Error::AlreadyDefined(_))));
+        let special = interpreter.eval_str("(eval (list (intern \"if\") nil 1 2))");
+        assert!(matches!(special, Err(Error::Unbound(_))));
+    }
+}
@@ -1,7 +1,14 @@
 pub mod environment;
 pub mod errors;
 pub mod evaluator;
+#[cfg(feature = "blocking")]
+pub mod interpreter;
 pub mod object;
 pub mod reader;
+pub mod symbol;
 
+#[cfg(feature = "blocking")]
+pub use interpreter::Interpreter;
 
+#[cfg(feature = "derive")]
+pub use lustre_derive::Lustre;
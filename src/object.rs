@@ -1,7 +1,15 @@
 use crate::errors::{self, Error};
+use crate::evaluator::Context;
+use crate::symbol::{Case, Symbol};
+use futures::future::BoxFuture;
+use std::any::Any;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::fmt::Display;
-use std::sync::Arc;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::sync::{Arc, RwLock};
 
 pub type RefObject = Arc<Option<Object>>;
 pub type ResultRefObject = errors::Result<RefObject>;
@@ -16,15 +24,38 @@ pub fn result_nil() -> ResultRefObject {
     Ok(Arc::new(None))
 }
 
+/// Builtins that only need their arguments. Those that evaluate code or
+/// reach the environment or the output are `AsyncOp`s instead.
 type Op = fn(RefObject) -> ResultRefObject;
+/// Operators that need the runtime context, e.g. to apply a lambda or write output.
+pub type AsyncOp = fn(RefObject, Context) -> BoxFuture<'static, ResultRefObject>;
+
+/// Host closures registered through `RefEnvironment::register`, given the
+/// context of the call. Each registration allocates its own `Arc`, which is
+/// the closure's identity.
+pub type NativeFn = Arc<dyn Fn(RefObject, &Context) -> ResultRefObject + Send + Sync>;
+
+/// Persistent map, so updates share structure with the original. The hasher
+/// has fixed keys, so entries print in the same order on every run.
+pub type Map = im::HashMap<RefObject, RefObject, BuildHasherDefault<DefaultHasher>>;
 
 pub enum Object {
     Integer(i32),
     IString(String),
+    Char(char),
     Cons(RefObject, RefObject),
+    /// A mutable array, shared by every reference to it.
+    Vector(RwLock<Vec<RefObject>>),
+    Map(Map),
     Lambda(RefObject, RefObject),
     Operator(String, Op),
-    Symbol(String),
+    AsyncOperator(String, AsyncOp),
+    Closure(String, NativeFn),
+    /// A host value passed through Lustre code untouched, with its type name.
+    Foreign(&'static str, Arc<dyn Any + Send + Sync>),
+    Symbol(Symbol),
+    /// `:name`, a symbol that evaluates to itself.
+    Keyword(Symbol),
 }
 
 unsafe impl Send for Object {
@@ -37,11 +68,94 @@ impl PartialEq for Object {
         match (self, other) {
             (Integer(v1), Integer(v2)) => v1 == v2,
             (IString(v1), IString(v2)) => v1 == v2,
-            (Cons(v11, v12), Cons(v21, v22)) => v11.as_ref() == v21.as_ref() && v12 == v22,
-            (Lambda(v11, v12), Lambda(v21, v22)) => v11.as_ref() == v21.as_ref() && v12 == v22,
-            (Operator(n1, _), Operator(n2, _)) => n1 == n2,
+            (Char(v1), Char(v2)) => v1 == v2,
+            (Cons(v11, v12), Cons(v21, v22)) => v11 == v21 && v12 == v22,
+            (Vector(v1), Vector(v2)) => {
+                std::ptr::eq(v1, v2) || *v1.read().unwrap() == *v2.read().unwrap()
+            }
+            (Map(v1), Map(v2)) => v1 == v2,
+            (Lambda(v11, v12), Lambda(v21, v22)) => v11 == v21 && v12 == v22,
+            (Operator(n1, f1), Operator(n2, f2)) => n1 == n2 && std::ptr::fn_addr_eq(*f1, *f2),
+            (AsyncOperator(n1, f1), AsyncOperator(n2, f2)) => {
+                n1 == n2 && std::ptr::fn_addr_eq(*f1, *f2)
+            }
+            (Closure(n1, f1), Closure(n2, f2)) => n1 == n2 && Arc::ptr_eq(f1, f2),
+            (Foreign(n1, v1), Foreign(n2, v2)) => n1 == n2 && Arc::ptr_eq(v1, v2),
             (Symbol(v1), Symbol(v2)) => v1 == v2,
-            (_, _) => false,
+            (Keyword(v1), Keyword(v2)) => v1 == v2,
+            // Listed per variant so that a new variant fails to compile here
+            // until its equality is defined.
+            (Integer(_), _)
+            | (IString(_), _)
+            | (Char(_), _)
+            | (Cons(_, _), _)
+            | (Vector(_), _)
+            | (Map(_), _)
+            | (Lambda(_, _), _)
+            | (Operator(_, _), _)
+            | (AsyncOperator(_, _), _)
+            | (Closure(_, _), _)
+            | (Foreign(_, _), _)
+            | (Symbol(_), _)
+            | (Keyword(_), _) => false,
+        }
+    }
+}
+
+impl Eq for Object {}
+
+/// Unlinks a list one cell at a time. The generated drop would recurse once
+/// per element and overflow the stack on long lists.
+impl Drop for Object {
+    fn drop(&mut self) {
+        if let Object::Cons(_, cdr) = self {
+            let mut next = std::mem::replace(cdr, nil());
+            // Stops at a cell still shared with another list, which keeps it.
+            while let Some(Some(Object::Cons(_, cdr))) = Arc::get_mut(&mut next) {
+                let following = std::mem::replace(cdr, nil());
+                next = following;
+            }
+        }
+    }
+}
+
+/// Hashes what `PartialEq` compares, so objects can be map keys.
+impl Hash for Object {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        use Object::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Integer(v) => v.hash(state),
+            IString(v) => v.hash(state),
+            Char(v) => v.hash(state),
+            Cons(car, cdr) => {
+                car.hash(state);
+                cdr.hash(state);
+            }
+            // Only for lookups: `map_key` keeps vectors out of stored keys.
+            Vector(v) => v.read().unwrap().hash(state),
+            // Independent of the iteration order, which differs between
+            // equal maps built in a different order.
+            Map(v) => {
+                let mut total = 0u64;
+                for entry in v.iter() {
+                    let mut hasher = DefaultHasher::new();
+                    entry.hash(&mut hasher);
+                    total = total.wrapping_add(hasher.finish());
+                }
+                v.len().hash(state);
+                total.hash(state);
+            }
+            Lambda(params, expression) => {
+                params.hash(state);
+                expression.hash(state);
+            }
+            Operator(n, _) | AsyncOperator(n, _) | Closure(n, _) => n.hash(state),
+            Symbol(v) | Keyword(v) => v.hash(state),
+            Foreign(n, v) => {
+                n.hash(state);
+                (Arc::as_ptr(v) as *const () as usize).hash(state);
+            }
         }
     }
 }
@@ -59,6 +173,42 @@ impl Debug for Object {
     }
 }
 
+/// Characters written by name rather than as themselves, e.g. `#\space`.
+pub const CHAR_NAMES: &[(&str, char)] = &[
+    ("space", ' '),
+    ("newline", '\n'),
+    ("tab", '\t'),
+    ("return", '\r'),
+    ("nul", '\0'),
+];
+
+/// Writes `ch` as a character literal the reader turns back into `ch`.
+fn write_char(f: &mut std::fmt::Formatter<'_>, ch: char) -> std::fmt::Result {
+    match CHAR_NAMES.iter().find(|(_, named)| *named == ch) {
+        Some((name, _)) => write!(f, "#\\{}", name),
+        None if ch.is_control() => write!(f, "#\\x{:x}", ch as u32),
+        None => write!(f, "#\\{}", ch),
+    }
+}
+
+/// Writes `text` as a string literal the reader turns back into `text`.
+fn write_escaped(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for ch in text.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            '\r' => write!(f, "\\r")?,
+            '\0' => write!(f, "\\0")?,
+            ch if ch.is_control() => write!(f, "\\u{{{:x}}}", ch as u32)?,
+            ch => write!(f, "{}", ch)?,
+        }
+    }
+    write!(f, "\"")
+}
+
 impl Object {
     fn helper_fmt(
         &self,
@@ -66,18 +216,48 @@ impl Object {
     ) -> std::result::Result<(), std::fmt::Error> {
         match self {
             Object::Integer(v) => write!(f, "{}", v),
-            Object::IString(v) => write!(f, "\"{}\"", v),
+            Object::IString(v) => write_escaped(f, v),
+            Object::Char(v) => write_char(f, *v),
             Object::Symbol(v) => write!(f, "{}", v),
+            Object::Keyword(v) => write!(f, ":{}", v),
             Object::Cons(car, cdr) => {
-                if let Some(v) = car.as_ref().as_ref() {
-                    write!(f, " {}", v)?
+                match car.as_ref() {
+                    Some(v) => write!(f, " {}", v)?,
+                    None => write!(f, " ()")?,
                 }
-                if let Some(v) = cdr.as_ref().as_ref() {
-                    v.helper_fmt(f)
-                } else {
-                    Ok(())
+                match cdr.as_ref() {
+                    Some(v @ Object::Cons(_, _)) => v.helper_fmt(f),
+                    Some(v) => write!(f, " . {}", v),
+                    None => Ok(()),
                 }
             }
+            Object::Vector(elements) => {
+                let elements = elements.read().unwrap();
+                if elements.is_empty() {
+                    return write!(f, "#()");
+                }
+                write!(f, "#(")?;
+                for element in elements.iter() {
+                    match element.as_ref() {
+                        Some(v) => write!(f, " {}", v)?,
+                        None => write!(f, " ()")?,
+                    }
+                }
+                write!(f, " )")
+            }
+            Object::Map(entries) => {
+                if entries.is_empty() {
+                    return write!(f, "{{}}");
+                }
+                write!(f, "{{")?;
+                for element in entries.iter().flat_map(|(key, value)| vec![key, value]) {
+                    match element.as_ref() {
+                        Some(v) => write!(f, " {}", v)?,
+                        None => write!(f, " ()")?,
+                    }
+                }
+                write!(f, " }}")
+            }
             Object::Lambda(params, expression) => {
                 write!(f, "( LAMBDA ")?;
                 if let Some(v) = params.as_ref().as_ref() {
@@ -91,7 +271,10 @@ impl Object {
                     write!(f, " )")
                 } 
             }
-            Object::Operator(n, _) => write!(f, "{}", n),
+            Object::Operator(n, _) | Object::AsyncOperator(n, _) | Object::Closure(n, _) => {
+                write!(f, "{}", n)
+            }
+            Object::Foreign(n, _) => write!(f, "#<FOREIGN {}>", n),
         }
     }
 }
@@ -128,10 +311,7 @@ impl Display for Object {
 }
 
 pub fn not_nil(obj: &RefObject) -> bool {
-    match obj.as_ref() {
-        Some(_) => true,
-        None => false,
-    }
+    obj.as_ref().is_some()
 }
 
 pub fn destructure_list<'a>(list: &'a RefObject) -> ResultDestrucuturedCons<'a> {
@@ -142,9 +322,60 @@ pub fn destructure_list<'a>(list: &'a RefObject) -> ResultDestrucuturedCons<'a>
     }
 }
 
-pub fn symbol_value(sym: &RefObject) -> errors::Result<String> {
+/// Collects the elements of a proper list.
+pub fn list_elements(list: &RefObject) -> errors::Result<Vec<RefObject>> {
+    let mut elements = Vec::new();
+    let mut next = list;
+    while not_nil(next) {
+        if let Some(Object::Cons(car, cdr)) = next.as_ref() {
+            elements.push(Arc::clone(car));
+            next = cdr;
+        } else {
+            return Err(Error::NotProperList);
+        }
+    }
+    Ok(elements)
+}
+
+/// Splits a possibly improper list into its elements and the final cdr,
+/// nil for a proper list and anything else for a dotted one.
+pub fn list_parts(list: &RefObject) -> (Vec<RefObject>, RefObject) {
+    let mut elements = Vec::new();
+    let mut next = list;
+    while let Some(Object::Cons(car, cdr)) = next.as_ref() {
+        elements.push(Arc::clone(car));
+        next = cdr;
+    }
+    (elements, Arc::clone(next))
+}
+
+/// Builds a list from `elements` ending in `tail` instead of nil.
+pub fn list_with_tail<I>(elements: I, tail: RefObject) -> RefObject
+where
+    I: IntoIterator<Item = RefObject>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    elements
+        .into_iter()
+        .rev()
+        .fold(tail, |list, element| Object::Cons(element, list).into())
+}
+
+pub fn list<I>(elements: I) -> RefObject
+where
+    I: IntoIterator<Item = RefObject>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    list_with_tail(elements, nil())
+}
+
+pub fn symbol(name: &str) -> RefObject {
+    Object::Symbol(Symbol::intern(name)).into()
+}
+
+pub fn symbol_value(sym: &RefObject) -> errors::Result<Symbol> {
     if let Some(Object::Symbol(value)) = sym.as_ref() {
-        Ok(value.to_string())
+        Ok(*value)
     } else {
         Err(Error::NotSymbol)
     }
@@ -157,3 +388,590 @@ pub fn integer_value(int: &RefObject) -> errors::Result<i32> {
         Err(Error::NotInteger)
     }
 }
+
+pub fn string_value(string: &RefObject) -> errors::Result<String> {
+    if let Some(Object::IString(value)) = string.as_ref() {
+        Ok(value.to_string())
+    } else {
+        Err(Error::NotString)
+    }
+}
+
+pub fn vector(elements: Vec<RefObject>) -> RefObject {
+    Object::Vector(RwLock::new(elements)).into()
+}
+
+pub fn vector_value(vector: &RefObject) -> errors::Result<&RwLock<Vec<RefObject>>> {
+    if let Some(Object::Vector(elements)) = vector.as_ref() {
+        Ok(elements)
+    } else {
+        Err(Error::NotVector)
+    }
+}
+
+pub fn map_value(map: &RefObject) -> errors::Result<&Map> {
+    if let Some(Object::Map(entries)) = map.as_ref() {
+        Ok(entries)
+    } else {
+        Err(Error::NotMap)
+    }
+}
+
+fn contains_vector(obj: &RefObject) -> bool {
+    let mut next = obj;
+    loop {
+        match next.as_ref() {
+            Some(Object::Cons(car, cdr)) => {
+                if contains_vector(car) {
+                    return true;
+                }
+                next = cdr;
+            }
+            Some(Object::Vector(_)) => return true,
+            Some(Object::Map(entries)) => {
+                return entries
+                    .iter()
+                    .any(|(key, value)| contains_vector(key) || contains_vector(value))
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Whether `target` is `obj` or can be reached from it.
+pub fn refers_to(obj: &RefObject, target: &RefObject) -> bool {
+    let mut next = obj;
+    loop {
+        if Arc::ptr_eq(next, target) {
+            return true;
+        }
+        match next.as_ref() {
+            Some(Object::Cons(car, cdr)) | Some(Object::Lambda(car, cdr)) => {
+                if refers_to(car, target) {
+                    return true;
+                }
+                next = cdr;
+            }
+            Some(Object::Vector(elements)) => {
+                return elements
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .any(|element| refers_to(element, target))
+            }
+            Some(Object::Map(entries)) => {
+                return entries
+                    .iter()
+                    .any(|(key, value)| refers_to(key, target) || refers_to(value, target))
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Checks that `key` may be used as a map key. Vectors can change after the
+/// key is hashed, so a key may not contain one anywhere.
+pub fn map_key(key: &RefObject) -> errors::Result<RefObject> {
+    if contains_vector(key) {
+        Err(Error::MutableKey)
+    } else {
+        Ok(Arc::clone(key))
+    }
+}
+
+pub fn char_value(ch: &RefObject) -> errors::Result<char> {
+    if let Some(Object::Char(value)) = ch.as_ref() {
+        Ok(*value)
+    } else {
+        Err(Error::NotChar)
+    }
+}
+
+/// Wraps a host value so it can be handed to Lustre code.
+pub fn foreign<T: Any + Send + Sync>(value: T) -> RefObject {
+    Object::Foreign(std::any::type_name::<T>(), Arc::new(value)).into()
+}
+
+/// Whether the object is a foreign value of type `T`.
+pub fn is_foreign<T: Any + Send + Sync>(obj: &RefObject) -> bool {
+    matches!(obj.as_ref(), Some(Object::Foreign(_, value)) if value.is::<T>())
+}
+
+/// Downcasts a foreign object back to the host value it wraps.
+pub fn foreign_value<T: Any + Send + Sync>(obj: &RefObject) -> errors::Result<Arc<T>> {
+    if let Some(Object::Foreign(_, value)) = obj.as_ref() {
+        if let Ok(value) = Arc::clone(value).downcast::<T>() {
+            return Ok(value);
+        }
+    }
+    Err(Error::NotForeign(std::any::type_name::<T>()))
+}
+
+/// Identity: both references point to the same object. Symbols are
+/// identified by name and every nil is the same nil.
+pub fn eq(a: &RefObject, b: &RefObject) -> bool {
+    match (a.as_ref(), b.as_ref()) {
+        (None, None) => true,
+        (Some(Object::Symbol(v1)), Some(Object::Symbol(v2))) => v1 == v2,
+        (Some(Object::Keyword(v1)), Some(Object::Keyword(v2))) => v1 == v2,
+        _ => Arc::ptr_eq(a, b),
+    }
+}
+
+/// Identity, or the same atomic value for numbers.
+pub fn eql(a: &RefObject, b: &RefObject) -> bool {
+    match (a.as_ref(), b.as_ref()) {
+        (Some(Object::Integer(v1)), Some(Object::Integer(v2))) => v1 == v2,
+        (Some(Object::Char(v1)), Some(Object::Char(v2))) => v1 == v2,
+        _ => eq(a, b),
+    }
+}
+
+/// Deep structural equality.
+pub fn equal(a: &RefObject, b: &RefObject) -> bool {
+    a == b
+}
+
+/// Builds an association list of `(KEY . value)` pairs with symbol keys, the
+/// representation `#[derive(Lustre)]` uses for structs with named fields.
+/// Keys are given in upper case and spelled as `case` spells builtins.
+pub fn record<'a, I>(fields: I, case: Case) -> RefObject
+where
+    I: IntoIterator<Item = (&'a str, RefObject)>,
+{
+    let pairs = fields
+        .into_iter()
+        .map(|(key, value)| Object::Cons(Object::Symbol(case.builtin(key)).into(), value).into())
+        .collect::<Vec<_>>();
+    list(pairs)
+}
+
+fn record_value(record: &RefObject, key: Symbol) -> errors::Result<Option<RefObject>> {
+    for pair in list_elements(record)? {
+        let (name, value) = destructure_list(&pair)?;
+        if let Some(Object::Symbol(name)) = name.as_ref() {
+            if *name == key {
+                return Ok(Some(Arc::clone(value)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Converts the value of the `key` pair of a record, failing with
+/// `Error::MissingField` when there is none. `key` is spelled as in `record`.
+pub fn record_field<T: FromLustre>(record: &RefObject, key: &str, case: Case) -> errors::Result<T> {
+    let key = case.builtin(key);
+    match record_value(record, key)? {
+        Some(value) => T::from_lustre_with_case(&value, case),
+        None => Err(Error::MissingField(key.to_string())),
+    }
+}
+
+/// Like `record_field` for an `Option` field, which a missing pair leaves
+/// `None`.
+pub fn optional_record_field<T: FromLustre>(
+    record: &RefObject,
+    key: &str,
+    case: Case,
+) -> errors::Result<Option<T>> {
+    match record_value(record, case.builtin(key))? {
+        Some(value) => Option::<T>::from_lustre_with_case(&value, case),
+        None => Ok(None),
+    }
+}
+
+/// Splits an enum value into its variant symbol and payload: either a bare
+/// symbol or a `(VARIANT . payload)` pair.
+pub fn variant(obj: &RefObject) -> errors::Result<(Symbol, RefObject)> {
+    match obj.as_ref() {
+        Some(Object::Symbol(name)) => Ok((*name, nil())),
+        _ => {
+            let (name, payload) = destructure_list(obj)?;
+            Ok((symbol_value(name)?, Arc::clone(payload)))
+        }
+    }
+}
+
+/// Conversion from a Lustre object into a Rust value.
+pub trait FromLustre: Sized {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self>;
+
+    /// Reads records and variants named as `case` names symbols. Only
+    /// containers and derived types need to override it.
+    fn from_lustre_with_case(obj: &RefObject, _case: Case) -> errors::Result<Self> {
+        Self::from_lustre(obj)
+    }
+}
+
+/// Conversion from a Rust value into a Lustre object. Fallible since not
+/// every Rust value fits, e.g. an `i64` beyond the range of `Object::Integer`.
+pub trait IntoLustre {
+    fn into_lustre(self) -> ResultRefObject;
+
+    /// Names records and variants as `case` names symbols, see
+    /// `FromLustre::from_lustre_with_case`.
+    fn into_lustre_with_case(self, _case: Case) -> ResultRefObject
+    where
+        Self: Sized,
+    {
+        self.into_lustre()
+    }
+}
+
+impl FromLustre for RefObject {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Ok(Arc::clone(obj))
+    }
+}
+
+impl IntoLustre for RefObject {
+    fn into_lustre(self) -> ResultRefObject {
+        Ok(self)
+    }
+}
+
+impl IntoLustre for Object {
+    fn into_lustre(self) -> ResultRefObject {
+        self.into()
+    }
+}
+
+impl<T: IntoLustre> IntoLustre for errors::Result<T> {
+    fn into_lustre(self) -> ResultRefObject {
+        self?.into_lustre()
+    }
+
+    fn into_lustre_with_case(self, case: Case) -> ResultRefObject {
+        self?.into_lustre_with_case(case)
+    }
+}
+
+impl FromLustre for () {
+    fn from_lustre(_: &RefObject) -> errors::Result<Self> {
+        Ok(())
+    }
+}
+
+impl IntoLustre for () {
+    fn into_lustre(self) -> ResultRefObject {
+        result_nil()
+    }
+}
+
+impl FromLustre for i32 {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        integer_value(obj)
+    }
+}
+
+impl IntoLustre for i32 {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::Integer(self).into()
+    }
+}
+
+impl FromLustre for i64 {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Ok(integer_value(obj)?.into())
+    }
+}
+
+impl IntoLustre for i64 {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::Integer(i32::try_from(self).map_err(|_| Error::Overflow)?).into()
+    }
+}
+
+impl FromLustre for f64 {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Ok(integer_value(obj)?.into())
+    }
+}
+
+/// Only integral values are representable, as Lustre has no floating point type.
+impl IntoLustre for f64 {
+    fn into_lustre(self) -> ResultRefObject {
+        if self.fract() == 0.0 && self >= i32::MIN.into() && self <= i32::MAX.into() {
+            Object::Integer(self as i32).into()
+        } else {
+            Err(Error::NotRepresentable)
+        }
+    }
+}
+
+impl FromLustre for String {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        string_value(obj)
+    }
+}
+
+impl IntoLustre for String {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::IString(self).into()
+    }
+}
+
+impl IntoLustre for &str {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::IString(self.to_string()).into()
+    }
+}
+
+impl FromLustre for char {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        char_value(obj)
+    }
+}
+
+impl IntoLustre for char {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::Char(self).into()
+    }
+}
+
+/// Anything but nil is true.
+impl FromLustre for bool {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Ok(not_nil(obj))
+    }
+}
+
+impl IntoLustre for bool {
+    fn into_lustre(self) -> ResultRefObject {
+        if self {
+            Object::Integer(1).into()
+        } else {
+            result_nil()
+        }
+    }
+}
+
+/// A host value of type `T` as a typed argument or result of a native function.
+pub struct Foreign<T>(pub Arc<T>);
+
+impl<T: Any + Send + Sync> FromLustre for Foreign<T> {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Ok(Foreign(foreign_value(obj)?))
+    }
+}
+
+impl<T: Any + Send + Sync> IntoLustre for Foreign<T> {
+    fn into_lustre(self) -> ResultRefObject {
+        Object::Foreign(std::any::type_name::<T>(), self.0).into()
+    }
+}
+
+impl<T: FromLustre> FromLustre for Vec<T> {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Self::from_lustre_with_case(obj, Case::default())
+    }
+
+    fn from_lustre_with_case(obj: &RefObject, case: Case) -> errors::Result<Self> {
+        list_elements(obj)?
+            .iter()
+            .map(|element| T::from_lustre_with_case(element, case))
+            .collect()
+    }
+}
+
+impl<T: IntoLustre> IntoLustre for Vec<T> {
+    fn into_lustre(self) -> ResultRefObject {
+        self.into_lustre_with_case(Case::default())
+    }
+
+    fn into_lustre_with_case(self, case: Case) -> ResultRefObject {
+        let elements = self
+            .into_iter()
+            .map(|element| element.into_lustre_with_case(case))
+            .collect::<errors::Result<Vec<_>>>()?;
+        Ok(list(elements))
+    }
+}
+
+/// `None` is nil. The mapping is lossy when `T` itself can be nil: `Some(false)`,
+/// `Some(vec![])` and `Some(None)` all convert to nil and come back as `None`.
+impl<T: FromLustre> FromLustre for Option<T> {
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Self::from_lustre_with_case(obj, Case::default())
+    }
+
+    fn from_lustre_with_case(obj: &RefObject, case: Case) -> errors::Result<Self> {
+        if not_nil(obj) {
+            Ok(Some(T::from_lustre_with_case(obj, case)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: IntoLustre> IntoLustre for Option<T> {
+    fn into_lustre(self) -> ResultRefObject {
+        self.into_lustre_with_case(Case::default())
+    }
+
+    fn into_lustre_with_case(self, case: Case) -> ResultRefObject {
+        match self {
+            Some(value) => value.into_lustre_with_case(case),
+            None => result_nil(),
+        }
+    }
+}
+
+/// Maps convert from and to association lists of `(key . value)` pairs.
+impl<K, V> FromLustre for HashMap<K, V>
+where
+    K: FromLustre + Eq + Hash,
+    V: FromLustre,
+{
+    fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+        Self::from_lustre_with_case(obj, Case::default())
+    }
+
+    fn from_lustre_with_case(obj: &RefObject, case: Case) -> errors::Result<Self> {
+        let mut map = HashMap::new();
+        for pair in list_elements(obj)? {
+            let (key, value) = destructure_list(&pair)?;
+            map.insert(
+                K::from_lustre_with_case(key, case)?,
+                V::from_lustre_with_case(value, case)?,
+            );
+        }
+        Ok(map)
+    }
+}
+
+impl<K: IntoLustre, V: IntoLustre> IntoLustre for HashMap<K, V> {
+    fn into_lustre(self) -> ResultRefObject {
+        self.into_lustre_with_case(Case::default())
+    }
+
+    fn into_lustre_with_case(self, case: Case) -> ResultRefObject {
+        let pairs = self
+            .into_iter()
+            .map(|(key, value)| {
+                Object::Cons(key.into_lustre_with_case(case)?, value.into_lustre_with_case(case)?).into()
+            })
+            .collect::<errors::Result<Vec<_>>>()?;
+        Ok(list(pairs))
+    }
+}
+
+/// Tuples convert from and to lists of the same length.
+macro_rules! tuple_conversions {
+    ($($name:ident $value:ident),+) => {
+        impl<$($name: FromLustre),+> FromLustre for ($($name,)+) {
+            fn from_lustre(obj: &RefObject) -> errors::Result<Self> {
+                Self::from_lustre_with_case(obj, Case::default())
+            }
+
+            fn from_lustre_with_case(obj: &RefObject, case: Case) -> errors::Result<Self> {
+                match list_elements(obj)?.as_slice() {
+                    [$($value),+] => Ok(($($name::from_lustre_with_case($value, case)?,)+)),
+                    _ => Err(Error::WrongArgumentCount),
+                }
+            }
+        }
+
+        impl<$($name: IntoLustre),+> IntoLustre for ($($name,)+) {
+            fn into_lustre(self) -> ResultRefObject {
+                self.into_lustre_with_case(Case::default())
+            }
+
+            fn into_lustre_with_case(self, case: Case) -> ResultRefObject {
+                let ($($value,)+) = self;
+                Ok(list(vec![$($value.into_lustre_with_case(case)?),+]))
+            }
+        }
+    };
+}
+
+tuple_conversions!(A a);
+tuple_conversions!(A a, B b);
+tuple_conversions!(A a, B b, C c);
+tuple_conversions!(A a, B b, C c, D d);
+tuple_conversions!(A a, B b, C c, D d, E e);
+
+/// Rust functions callable with a list of Lustre arguments, converting each
+/// argument with `FromLustre` and the result with `IntoLustre`. Implemented
+/// for functions and closures of up to five arguments, see
+/// `RefEnvironment::register_function`.
+pub trait NativeFunction<Args>: Send + Sync + 'static {
+    /// Converts records and variants as `case` names symbols.
+    fn call(&self, arguments: &RefObject, case: Case) -> ResultRefObject;
+}
+
+macro_rules! native_function {
+    ($($name:ident $value:ident),*) => {
+        impl<F, R, $($name),*> NativeFunction<($($name,)*)> for F
+        where
+            F: Fn($($name),*) -> R + Send + Sync + 'static,
+            R: IntoLustre,
+            $($name: FromLustre,)*
+        {
+            fn call(&self, arguments: &RefObject, case: Case) -> ResultRefObject {
+                match list_elements(arguments)?.as_slice() {
+                    [$($value),*] => {
+                        (self)($($name::from_lustre_with_case($value, case)?),*).into_lustre_with_case(case)
+                    }
+                    _ => Err(Error::WrongArgumentCount),
+                }
+            }
+        }
+    };
+}
+
+native_function!();
+native_function!(A a);
+native_function!(A a, B b);
+native_function!(A a, B b, C c);
+native_function!(A a, B b, C c, D d);
+native_function!(A a, B b, C c, D d, E e);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_test() {
+        let value = (7i64, String::from("seven"), vec![Some(true), None]).into_lustre().unwrap();
+        assert_eq!("( 7 \"seven\" ( 1 () ) )", format!("{}", value.as_ref().as_ref().unwrap()));
+        let back = <(i64, String, Vec<Option<bool>>)>::from_lustre(&value).unwrap();
+        assert_eq!((7, String::from("seven"), vec![Some(true), None]), back);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("a"), 1i32);
+        let alist = map.clone().into_lustre().unwrap();
+        assert_eq!(map, HashMap::<String, i32>::from_lustre(&alist).unwrap());
+
+        assert!(matches!(i64::MAX.into_lustre(), Err(Error::Overflow)));
+        assert!(matches!(0.5f64.into_lustre(), Err(Error::NotRepresentable)));
+        assert!(matches!(String::from_lustre(&Object::Integer(1).into()), Err(Error::NotString)));
+    }
+
+    #[test]
+    fn option_collapse_test() {
+        let value = Some(false).into_lustre().unwrap();
+        assert!(!not_nil(&value));
+        assert_eq!(None, Option::<bool>::from_lustre(&value).unwrap());
+        assert_eq!(None, Option::<Vec<i32>>::from_lustre(&Some(Vec::<i32>::new()).into_lustre().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn long_list_drop_test() {
+        let tail = list((0..1_000_000).map(|_| nil()));
+        let head: RefObject = Object::Cons(nil(), Arc::clone(&tail)).into();
+        drop(head);
+        assert_eq!(1_000_000, list_elements(&tail).unwrap().len());
+        drop(tail);
+    }
+
+    #[test]
+    fn closure_identity_test() {
+        let closure = |function: &NativeFn| Object::Closure(String::from("F"), Arc::clone(function));
+        let first: NativeFn = Arc::new(|_, _| result_nil());
+        let second: NativeFn = Arc::new(|_, _| result_nil());
+        assert!(closure(&first) == closure(&first));
+        assert!(closure(&first) != closure(&second));
+    }
+}
@@ -1,9 +1,10 @@
 pub mod tokenizer;
 
-use crate::object::{nil, result_nil, Object, RefObject};
+use crate::object::{map_key, nil, result_nil, vector, Map, Object, RefObject};
 use crate::reader::tokenizer::*;
+use crate::symbol::Case;
 
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 use std::sync::Arc;
 
 pub struct Reader<T>
@@ -11,11 +12,14 @@ where
     T: Iterator,
 {
     tokenizer: Tokenizer<T>,
+    case: Case,
+    /// Set once iterating has returned an error.
+    failed: bool,
 }
 
 macro_rules! r#return {
     ($obj:ident; $value:expr) => {
-        return Ok(Arc::new(Some(Object::$obj($value))));
+        return Ok(Arc::new(Some(Object::$obj($value))))
     };
     ($value:expr) => {
         return Ok($value);
@@ -27,52 +31,151 @@ where
     T: Iterator<Item = std::result::Result<u8, std::io::Error>>,
 {
     pub fn new(tokenizer: Tokenizer<T>) -> Self {
-        Self { tokenizer }
+        Self {
+            tokenizer,
+            case: Case::default(),
+            failed: false,
+        }
+    }
+    /// Reads identifiers as `case` does, e.g. keeping them as written.
+    pub fn with_case(self, case: Case) -> Self {
+        Self { case, ..self }
+    }
+    /// Reads the next top-level form, or `None` at the end of the input. Input
+    /// ending inside a form is `Error::Incomplete`, so a REPL can ask for more
+    /// lines.
+    pub fn read(&mut self) -> Result<Option<RefObject>> {
+        match self.tokenizer.token()? {
+            Some(token) => self.read_token(token).map(Some),
+            None => Ok(None),
+        }
     }
-    pub fn read(&mut self) -> Result<RefObject> {
-        if let Some(token) = self.tokenizer.token()? {
-            match token {
-                Token::Integer(s) => {
-                    let value = s.parse::<i32>()?;
+    /// Reads the form starting with `token`.
+    fn read_token(&mut self, token: Token) -> Result<RefObject> {
+        match token {
+            Token::Integer(s) => {
+                let value = s.parse::<i32>()?;
+                r#return!(Integer; value);
+            }
+            Token::Text(s) => r#return!(IString; s),
+            Token::Char(ch) => r#return!(Char; ch),
+            Token::Keyword(s) => r#return!(Keyword; self.case.symbol(&s)),
+            Token::Identifier(s) => {
+                if let Ok(value) = s.parse::<i32>() {
                     r#return!(Integer; value);
                 }
-                Token::Text(s) => r#return!(IString; s),
-                Token::Identifier(s) => r#return!(Symbol; s.to_uppercase() ),
-                Token::OpenList => self.read_list(),
-                Token::Quote => Ok(Arc::new(Some(Object::Cons(
-                    Arc::new(Some(Object::Symbol(String::from("QUOTE")))),
-                    Arc::new(Some(Object::Cons(self.read()?, nil()))),
-                )))),
-
-                Token::NoToken => {
-                    panic!("Inconsistent state sice NoToken isn't a valid return value.")
-                }
-                Token::Quasiquote => unimplemented!(),
-                Token::Unquote => unimplemented!(),
-                Token::CloseList => panic!("Unexpected end of a list."),
-                Token::Invalid(s) => unimplemented!("{:?}", s),
+                r#return!(Symbol; self.case.symbol(&s))
             }
-        } else {
-            Ok(Arc::new(None))
+            Token::OpenList => self.read_list(),
+            Token::OpenVector => self.read_vector(Token::CloseList),
+            Token::OpenBracket => self.read_vector(Token::CloseBracket),
+            Token::OpenBrace => self.read_map(),
+            Token::Quote => Ok(Arc::new(Some(Object::Cons(
+                Arc::new(Some(Object::Symbol(self.case.builtin("QUOTE")))),
+                Arc::new(Some(Object::Cons(self.read_next()?, nil()))),
+            )))),
+
+            Token::NoToken => {
+                panic!("Inconsistent state sice NoToken isn't a valid return value.")
+            }
+            Token::Quasiquote => Err(Error::Syntax(String::from("quasiquote is not supported"))),
+            Token::Unquote => Err(Error::Syntax(String::from("unquote is not supported"))),
+            Token::CloseList => Err(Error::Syntax(String::from("unexpected )"))),
+            Token::CloseBracket => Err(Error::Syntax(String::from("unexpected ]"))),
+            Token::CloseBrace => Err(Error::Syntax(String::from("unexpected }"))),
+            Token::Invalid(s) => Err(Error::Syntax(format!("invalid character {}", s))),
+        }
+    }
+    /// Reads a form that must be there, e.g. after a quote.
+    fn read_next(&mut self) -> Result<RefObject> {
+        match self.tokenizer.token()? {
+            Some(token) => self.read_token(token),
+            None => Err(Error::Incomplete),
         }
     }
     fn read_list(&mut self) -> Result<RefObject> {
-        if let Some(token) = self.tokenizer.token()? {
-            if let Token::CloseList = token {
-                result_nil()
-            } else {
+        match self.tokenizer.token()? {
+            Some(Token::Identifier(dot)) if dot == "." => {
+                Err(Error::Syntax(String::from("missing car before .")))
+            }
+            Some(token) => {
                 self.tokenizer.putback(token);
-                Ok(Arc::new(Some(Object::Cons(
-                    self.read()?,
-                    self.read_list()?,
-                ))))
+                self.read_list_rest()
             }
-        } else {
-            panic!("Unexpected end of stream!");
+            None => Err(Error::Incomplete),
+        }
+    }
+    /// Reads the elements of a vector up to `close`.
+    fn read_vector(&mut self, close: Token) -> Result<RefObject> {
+        let mut elements = Vec::new();
+        loop {
+            match self.tokenizer.token()? {
+                Some(token) if token == close => return Ok(vector(elements)),
+                Some(token) => elements.push(self.read_token(token)?),
+                None => return Err(Error::Incomplete),
+            }
+        }
+    }
+    /// Reads `key value` pairs up to the `}`, later keys replacing earlier ones.
+    fn read_map(&mut self) -> Result<RefObject> {
+        let mut entries = Map::default();
+        loop {
+            let key = match self.tokenizer.token()? {
+                Some(Token::CloseBrace) => return Ok(Arc::new(Some(Object::Map(entries)))),
+                Some(token) => map_key(&self.read_token(token)?)?,
+                None => return Err(Error::Incomplete),
+            };
+            match self.tokenizer.token()? {
+                Some(Token::CloseBrace) => {
+                    return Err(Error::Syntax(String::from("missing value in map literal")))
+                }
+                Some(token) => {
+                    entries.insert(key, self.read_token(token)?);
+                }
+                None => return Err(Error::Incomplete),
+            }
+        }
+    }
+    /// Reads the remaining elements of a list, which may end in `. cdr`.
+    fn read_list_rest(&mut self) -> Result<RefObject> {
+        match self.tokenizer.token()? {
+            Some(Token::CloseList) => result_nil(),
+            Some(Token::Identifier(dot)) if dot == "." => {
+                let cdr = self.read_next()?;
+                match self.tokenizer.token()? {
+                    Some(Token::CloseList) => Ok(cdr),
+                    Some(_) => Err(Error::Syntax(String::from("more than one form after ."))),
+                    None => Err(Error::Incomplete),
+                }
+            }
+            Some(token) => Ok(Arc::new(Some(Object::Cons(
+                self.read_token(token)?,
+                self.read_list_rest()?,
+            )))),
+            None => Err(Error::Incomplete),
         }
     }
 }
 
+/// Iterates over the top-level forms, ending at the end of the input or
+/// after the first error, since the reader can't tell where the next form
+/// would start.
+impl<T> Iterator for Reader<T>
+where
+    T: Iterator<Item = std::result::Result<u8, std::io::Error>>,
+{
+    type Item = Result<RefObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let form = self.read().transpose();
+        self.failed = matches!(form, Some(Err(_)));
+        form
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,7 +188,7 @@ mod tests {
         let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
         let mut reader = Reader::new(tokenizer);
         let a = reader.read();
-        let b = a.unwrap();
+        let b = a.unwrap().unwrap();
         if let Some(object) = b.as_ref() {
             eprintln!("result: {}", object);
 
@@ -97,4 +200,107 @@ mod tests {
             panic!("Ooops! Not an object...")
         }
     }
+
+    #[test]
+    fn incomplete_test() {
+        for input in &["(a (b c)", "'", "\"abc", "(a '"] {
+            let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+            let mut reader = Reader::new(tokenizer);
+            assert!(matches!(reader.read(), Err(Error::Incomplete)), "{}", input);
+        }
+        let tokenizer = Tokenizer::new(Cursor::new(")").bytes());
+        assert!(matches!(Reader::new(tokenizer).read(), Err(Error::Syntax(_))));
+    }
+
+    #[test]
+    fn iterator_test() {
+        let forms = |input: &str| {
+            let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+            Reader::new(tokenizer).collect::<Result<Vec<_>>>()
+        };
+        assert!(forms("").unwrap().is_empty());
+        assert!(forms("  ; only a comment\n").unwrap().is_empty());
+        let read = forms("() nil 1").unwrap();
+        assert_eq!(3, read.len());
+        assert!(read[0].is_none());
+        assert_eq!(Some(Object::Symbol(crate::symbol::Symbol::intern("NIL"))), *read[1]);
+        assert!(matches!(forms("1 (2"), Err(Error::Incomplete)));
+
+        let tokenizer = Tokenizer::new(Cursor::new("1 ) 2").bytes());
+        let read = Reader::new(tokenizer).collect::<Vec<_>>();
+        assert_eq!(2, read.len());
+        assert!(matches!(read[1], Err(Error::Syntax(_))));
+
+        let tokenizer = Tokenizer::new(Cursor::new("()").bytes());
+        let mut reader = Reader::new(tokenizer);
+        assert!(matches!(reader.read(), Ok(Some(_))));
+        assert!(matches!(reader.read(), Ok(None)));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn string_round_trip_test() {
+        let text = "tab\there \"quoted\" back\\slash\nnew line \u{7} \u{1F600}";
+        let printed = format!("{}", Object::IString(String::from(text)));
+        assert_eq!(
+            "\"tab\\there \\\"quoted\\\" back\\\\slash\\nnew line \\u{7} \u{1F600}\"",
+            printed
+        );
+        let tokenizer = Tokenizer::new(Cursor::new(printed).bytes());
+        let read = Reader::new(tokenizer).read().unwrap().unwrap();
+        assert_eq!(Some(Object::IString(String::from(text))), *read);
+    }
+
+    #[test]
+    fn dotted_pair_test() {
+        let read = |input: &str| {
+            let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+            Reader::new(tokenizer).next().unwrap()
+        };
+        for (input, printed) in &[
+            ("(a . b)", "( A . B )"),
+            ("(1 2 . (3 . 4))", "( 1 2 3 . 4 )"),
+            ("(() . ())", "( () )"),
+            ("((a . 1) (b . \"x\"))", "( ( A . 1 ) ( B . \"x\" ) )"),
+            ("(a . 'b)", "( A QUOTE B )"),
+        ] {
+            let obj = read(input).unwrap();
+            assert_eq!(*printed, format!("{}", obj.as_ref().as_ref().unwrap()));
+            assert_eq!(obj, read(printed).unwrap());
+        }
+        for input in &["(. a)", "(a . b c)", "(a .)"] {
+            assert!(matches!(read(input), Err(Error::Syntax(_))), "{}", input);
+        }
+        assert!(matches!(read("(a . b"), Err(Error::Incomplete)));
+    }
+
+    #[test]
+    fn vector_test() {
+        let read = |input: &str| {
+            let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+            Reader::new(tokenizer).next().unwrap()
+        };
+        let obj = read("[1 #(a) ()]").unwrap();
+        assert_eq!("#( 1 #( A ) () )", format!("{}", obj.as_ref().as_ref().unwrap()));
+        assert_eq!(obj, read("#(1 [a] ())").unwrap());
+        assert!(matches!(read("[1 2)"), Err(Error::Syntax(_))));
+        assert!(matches!(read("(1 2]"), Err(Error::Syntax(_))));
+        assert!(matches!(read("#(1 2"), Err(Error::Incomplete)));
+    }
+
+    #[test]
+    fn map_test() {
+        let read = |input: &str| {
+            let tokenizer = Tokenizer::new(Cursor::new(input).bytes());
+            Reader::new(tokenizer).next().unwrap()
+        };
+        let obj = read("{a {b 2}}").unwrap();
+        assert_eq!("{ A { B 2 } }", format!("{}", obj.as_ref().as_ref().unwrap()));
+        assert_eq!(read("{x 1 y 2 z 3}").unwrap(), read("{z 3 x 0 y 2 x 1}").unwrap());
+        assert!(matches!(read("{a 1 b}"), Err(Error::Syntax(_))));
+        assert!(matches!(read("{a 1)"), Err(Error::Syntax(_))));
+        assert!(matches!(read("{a 1"), Err(Error::Incomplete)));
+        assert!(matches!(read("{[1] 2}"), Err(Error::MutableKey)));
+        assert!(matches!(read("{(a {b [1]}) 2}"), Err(Error::MutableKey)));
+    }
 }
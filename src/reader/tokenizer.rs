@@ -1,26 +1,60 @@
 //#![allow(unused)]
-use crate::errors::Result;
+use crate::errors::{Error, Result};
+use crate::object::CHAR_NAMES;
 use rustf8::Utf8Iterator;
 use std::fmt::Debug;
 
 pub enum Token {
     NoToken,
     Identifier(String),
+    /// `:name`, without the colon.
+    Keyword(String),
     Integer(String),
     //Symbol(String),
     Text(String),
+    Char(char),
     Quote,
     Quasiquote,
     Unquote,
     OpenList,
     CloseList,
+    /// `#(`, closed by a `)`.
+    OpenVector,
+    OpenBracket,
+    CloseBracket,
+    OpenBrace,
+    CloseBrace,
     Invalid(String),
 }
 enum State {
     Begin,
     DecodingIdentifier,
+    DecodingKeyword,
     DecodingInteger,
     DecodingText,
+    /// After a `\` inside a string.
+    TextEscape,
+    /// After `\u`, expecting the `{`.
+    TextUnicodeOpen,
+    /// Inside `\u{...}`, with the hex digits read so far.
+    TextUnicode(String),
+    /// After `#r`, with the number of `#` read before the `"`.
+    RawStringOpen(usize),
+    /// Inside `#r"..."`, with the number of `#` that must follow the closing `"`.
+    RawString(usize),
+    /// After a `"` in a raw string, with the expected and seen number of `#`.
+    RawStringClose(usize, usize),
+    /// After `#\`, with the character or its name read so far.
+    DecodingChar(String),
+    /// After a `#`, deciding what the dispatch character means.
+    Dispatch,
+    LineComment,
+    /// Inside `#| ... |#`, with the nesting depth.
+    BlockComment(usize),
+    BlockCommentBar(usize),
+    BlockCommentHash(usize),
+    /// After `#_`, the next form has to be skipped.
+    DatumComment,
     FinishedToken,
     Invalid,
 }
@@ -32,11 +66,18 @@ impl PartialEq for Token {
             (NoToken, NoToken) => true,
             (OpenList, OpenList) => true,
             (CloseList, CloseList) => true,
+            (OpenVector, OpenVector) => true,
+            (OpenBracket, OpenBracket) => true,
+            (CloseBracket, CloseBracket) => true,
+            (OpenBrace, OpenBrace) => true,
+            (CloseBrace, CloseBrace) => true,
             (Quote, Quote) => true,
             (Quasiquote, Quasiquote) => true,
             (Unquote, Unquote) => true,
             (Text(a), Text(b)) => a == b,
+            (Char(a), Char(b)) => a == b,
             (Identifier(a), Identifier(b)) => a == b,
+            (Keyword(a), Keyword(b)) => a == b,
             (Integer(a), Integer(b)) => a == b,
             //(Symbol(a), Symbol(b)) => a == b,
             (Invalid(a), Invalid(b)) => a == b,
@@ -51,11 +92,18 @@ impl Clone for Token {
             NoToken => NoToken,
             OpenList => OpenList,
             CloseList => CloseList,
+            OpenVector => OpenVector,
+            OpenBracket => OpenBracket,
+            CloseBracket => CloseBracket,
+            OpenBrace => OpenBrace,
+            CloseBrace => CloseBrace,
             Quote => Quote,
             Quasiquote => Quasiquote,
             Unquote => Unquote,
             Text(a) => Text(a.to_string()),
+            Char(a) => Char(*a),
             Identifier(a) => Identifier(a.to_string()),
+            Keyword(a) => Keyword(a.to_string()),
             Integer(a) => Integer(a.to_string()),
             //Symbol(a) => Symbol(a.to_string()),
             Invalid(a) => Invalid(a.to_string()),
@@ -69,11 +117,18 @@ impl Debug for Token {
             NoToken => f.debug_struct("None").finish(),
             OpenList => f.debug_struct("OpenList").finish(),
             CloseList => f.debug_struct("CloseList").finish(),
+            OpenVector => f.debug_struct("OpenVector").finish(),
+            OpenBracket => f.debug_struct("OpenBracket").finish(),
+            CloseBracket => f.debug_struct("CloseBracket").finish(),
+            OpenBrace => f.debug_struct("OpenBrace").finish(),
+            CloseBrace => f.debug_struct("CloseBrace").finish(),
             Quote => f.debug_struct("Quote").finish(),
             Quasiquote => f.debug_struct("Quasiquote").finish(),
             Unquote => f.debug_struct("Unquote").finish(),
             Text(a) => f.debug_struct("Text").field("string", a).finish(),
+            Char(a) => f.debug_struct("Char").field("char", a).finish(),
             Identifier(a) => f.debug_struct("Identifier").field("string", a).finish(),
+            Keyword(a) => f.debug_struct("Keyword").field("string", a).finish(),
             Integer(a) => f.debug_struct("Integer").field("string", a).finish(),
             //Symbol(a) => f.debug_struct("Symbol").field("string", a).finish(),
             Invalid(a) => f.debug_struct("Invalid").field("string", a).finish(),
@@ -81,6 +136,44 @@ impl Debug for Token {
     }
 }
 
+/// Decodes the text after `#\`: a single character, a name such as `space`
+/// or a hex code such as `x41`.
+fn char_token(name: &str) -> Token {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Token::Char(ch);
+    }
+    let lowercase = name.to_lowercase();
+    let named = CHAR_NAMES
+        .iter()
+        .find(|(n, _)| *n == lowercase)
+        .map(|(_, ch)| *ch);
+    let coded = || {
+        lowercase
+            .strip_prefix('x')
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(std::char::from_u32)
+    };
+    match named.or_else(coded) {
+        Some(ch) => Token::Char(ch),
+        None => Token::Invalid(format!("#\\{}", name)),
+    }
+}
+
+/// A lone `:` stays the one-character identifier it always was.
+fn keyword_token(name: &str) -> Token {
+    if name.is_empty() {
+        Token::Identifier(String::from(":"))
+    } else {
+        Token::Keyword(name.to_string())
+    }
+}
+
+/// Punctuation that may appear in identifiers such as `<=` or `copy-list`.
+fn is_symbol_constituent(ch: char) -> bool {
+    ch.is_alphanumeric() || "!$%&*+-./<=>?@^_~".contains(ch)
+}
+
 pub struct Tokenizer<T>
 where
     T: Iterator,
@@ -110,28 +203,63 @@ where
     fn state_machine(&mut self, ch: Option<char>) {
         self.state = match ch {
             None => match &self.state {
-                (State::Invalid, _) | (State::FinishedToken, _) | (State::Begin, _) => {
-                    (State::FinishedToken, Token::NoToken)
+                (State::Invalid, _)
+                | (State::FinishedToken, _)
+                | (State::Begin, _)
+                | (State::LineComment, _) => (State::FinishedToken, Token::NoToken),
+                (State::Dispatch, _) => {
+                    (State::FinishedToken, Token::Identifier(String::from("#")))
                 }
                 (State::DecodingIdentifier, Token::Identifier(id)) => {
                     (State::FinishedToken, Token::Identifier(id.to_string()))
                 }
+                (State::DecodingKeyword, Token::Keyword(name)) => {
+                    (State::FinishedToken, keyword_token(name))
+                }
                 (State::DecodingInteger, Token::Integer(num)) => {
                     (State::FinishedToken, Token::Integer(num.to_string()))
                 }
                 (State::DecodingText, Token::Text(txt)) => {
                     (State::FinishedToken, Token::Text(txt.to_string()))
                 }
+                (State::DecodingChar(name), _) if !name.is_empty() => {
+                    (State::FinishedToken, char_token(name))
+                }
+                (State::DecodingChar(_), _)
+                | (State::BlockComment(_), _)
+                | (State::BlockCommentBar(_), _)
+                | (State::BlockCommentHash(_), _)
+                | (State::DatumComment, _)
+                | (State::TextEscape, _)
+                | (State::TextUnicodeOpen, _)
+                | (State::TextUnicode(_), _)
+                | (State::RawStringOpen(_), _)
+                | (State::RawString(_), _)
+                | (State::RawStringClose(..), _) => {
+                    panic!("Incomplete input must be handled by token().")
+                }
                 (State::DecodingIdentifier, _)
+                | (State::DecodingKeyword, _)
                 | (State::DecodingInteger, _)
                 | (State::DecodingText, _) => panic!("Inconsistent state!"),
             },
             Some(ch) => match &self.state {
-                (State::Invalid, _) | (State::FinishedToken, _) | (State::Begin, _) => {
+                (State::Invalid, _)
+                | (State::FinishedToken, _)
+                | (State::Begin, _)
+                | (State::DatumComment, _) => {
                     if ch == '(' {
                         (State::FinishedToken, Token::OpenList)
                     } else if ch == ')' {
                         (State::FinishedToken, Token::CloseList)
+                    } else if ch == '[' {
+                        (State::FinishedToken, Token::OpenBracket)
+                    } else if ch == ']' {
+                        (State::FinishedToken, Token::CloseBracket)
+                    } else if ch == '{' {
+                        (State::FinishedToken, Token::OpenBrace)
+                    } else if ch == '}' {
+                        (State::FinishedToken, Token::CloseBrace)
                     } else if ch == ',' {
                         (State::FinishedToken, Token::Unquote)
                     } else if ch == '`' {
@@ -140,12 +268,18 @@ where
                         (State::FinishedToken, Token::Quote)
                     } else if ch == '"' {
                         (State::DecodingText, Token::Text(String::new()))
+                    } else if ch == ';' {
+                        (State::LineComment, Token::NoToken)
+                    } else if ch == ':' {
+                        (State::DecodingKeyword, Token::Keyword(String::new()))
+                    } else if ch == '#' {
+                        (State::Dispatch, Token::NoToken)
                     } else if ch.is_whitespace() {
                         (State::Begin, Token::NoToken)
-                    } else if ch.is_alphabetic() || ch == '_' {
-                        (State::DecodingIdentifier, Token::Identifier(ch.to_string()))
                     } else if ch.is_numeric() {
                         (State::DecodingInteger, Token::Integer(ch.to_string()))
+                    } else if is_symbol_constituent(ch) {
+                        (State::DecodingIdentifier, Token::Identifier(ch.to_string()))
                     } else if ch.is_ascii_punctuation() {
                         (State::FinishedToken, Token::Identifier(ch.to_string()))
                     } else {
@@ -155,7 +289,7 @@ where
                 (State::DecodingIdentifier, Token::Identifier(id)) => {
                     if ch.is_whitespace() {
                         (State::FinishedToken, Token::Identifier(id.to_string()))
-                    } else if ch.is_alphanumeric() || ch == '_' {
+                    } else if is_symbol_constituent(ch) {
                         (
                             State::DecodingIdentifier,
                             Token::Identifier(id.to_string() + &ch.to_string()),
@@ -165,10 +299,21 @@ where
                         (State::FinishedToken, Token::Identifier(id.to_string()))
                     }
                 }
+                (State::DecodingKeyword, Token::Keyword(name)) => {
+                    if is_symbol_constituent(ch) {
+                        (
+                            State::DecodingKeyword,
+                            Token::Keyword(name.to_string() + &ch.to_string()),
+                        )
+                    } else {
+                        self.chiter.unget(ch);
+                        (State::FinishedToken, keyword_token(name))
+                    }
+                }
                 (State::DecodingInteger, Token::Integer(num)) => {
                     if ch.is_whitespace() {
                         (State::FinishedToken, Token::Integer(num.to_string()))
-                    } else if ch.is_digit(10) {
+                    } else if ch.is_ascii_digit() {
                         (
                             State::DecodingInteger,
                             Token::Integer(num.to_string() + &ch.to_string()),
@@ -181,6 +326,8 @@ where
                 (State::DecodingText, Token::Text(txt)) => {
                     if ch == '"' {
                         (State::FinishedToken, Token::Text(txt.to_string()))
+                    } else if ch == '\\' {
+                        (State::TextEscape, Token::Text(txt.to_string()))
                     } else {
                         (
                             State::DecodingText,
@@ -188,13 +335,216 @@ where
                         )
                     }
                 }
+                (State::TextEscape, Token::Text(txt)) => {
+                    let escaped = match ch {
+                        '"' => Some('"'),
+                        '\\' => Some('\\'),
+                        'n' => Some('\n'),
+                        't' => Some('\t'),
+                        'r' => Some('\r'),
+                        '0' => Some('\0'),
+                        _ => None,
+                    };
+                    match escaped {
+                        Some(escaped) => (
+                            State::DecodingText,
+                            Token::Text(txt.to_string() + &escaped.to_string()),
+                        ),
+                        None if ch == 'u' => (State::TextUnicodeOpen, Token::Text(txt.to_string())),
+                        None => (State::FinishedToken, Token::Invalid(format!("\\{}", ch))),
+                    }
+                }
+                (State::TextUnicodeOpen, Token::Text(txt)) => {
+                    if ch == '{' {
+                        (
+                            State::TextUnicode(String::new()),
+                            Token::Text(txt.to_string()),
+                        )
+                    } else {
+                        (State::FinishedToken, Token::Invalid(format!("\\u{}", ch)))
+                    }
+                }
+                (State::TextUnicode(hex), Token::Text(txt)) => {
+                    if ch.is_ascii_hexdigit() && hex.len() < 6 {
+                        (
+                            State::TextUnicode(hex.to_string() + &ch.to_string()),
+                            Token::Text(txt.to_string()),
+                        )
+                    } else if ch == '}' {
+                        match u32::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(std::char::from_u32)
+                        {
+                            Some(decoded) => (
+                                State::DecodingText,
+                                Token::Text(txt.to_string() + &decoded.to_string()),
+                            ),
+                            None => (
+                                State::FinishedToken,
+                                Token::Invalid(format!("\\u{{{}}}", hex)),
+                            ),
+                        }
+                    } else {
+                        (
+                            State::FinishedToken,
+                            Token::Invalid(format!("\\u{{{}{}", hex, ch)),
+                        )
+                    }
+                }
+                (State::RawStringOpen(hashes), _) => {
+                    if ch == '#' {
+                        (State::RawStringOpen(hashes + 1), Token::NoToken)
+                    } else if ch == '"' {
+                        (State::RawString(*hashes), Token::Text(String::new()))
+                    } else {
+                        (
+                            State::FinishedToken,
+                            Token::Invalid(format!("#r{}{}", "#".repeat(*hashes), ch)),
+                        )
+                    }
+                }
+                (State::RawString(hashes), Token::Text(txt)) => {
+                    if ch == '"' && *hashes == 0 {
+                        (State::FinishedToken, Token::Text(txt.to_string()))
+                    } else if ch == '"' {
+                        (
+                            State::RawStringClose(*hashes, 0),
+                            Token::Text(txt.to_string()),
+                        )
+                    } else {
+                        (
+                            State::RawString(*hashes),
+                            Token::Text(txt.to_string() + &ch.to_string()),
+                        )
+                    }
+                }
+                (State::RawStringClose(hashes, seen), Token::Text(txt)) => {
+                    if ch == '#' && seen + 1 == *hashes {
+                        (State::FinishedToken, Token::Text(txt.to_string()))
+                    } else if ch == '#' {
+                        (
+                            State::RawStringClose(*hashes, seen + 1),
+                            Token::Text(txt.to_string()),
+                        )
+                    } else {
+                        // Not the end after all, the quote and hashes are content.
+                        let txt = txt.to_string() + "\"" + &"#".repeat(*seen);
+                        if ch == '"' {
+                            (State::RawStringClose(*hashes, 0), Token::Text(txt))
+                        } else {
+                            (
+                                State::RawString(*hashes),
+                                Token::Text(txt + &ch.to_string()),
+                            )
+                        }
+                    }
+                }
+                (State::Dispatch, _) => {
+                    if ch == '|' {
+                        (State::BlockComment(1), Token::NoToken)
+                    } else if ch == '_' {
+                        (State::DatumComment, Token::NoToken)
+                    } else if ch == 'r' {
+                        (State::RawStringOpen(0), Token::NoToken)
+                    } else if ch == '(' {
+                        (State::FinishedToken, Token::OpenVector)
+                    } else if ch == '\\' {
+                        (State::DecodingChar(String::new()), Token::NoToken)
+                    } else {
+                        self.chiter.unget(ch);
+                        (State::FinishedToken, Token::Identifier(String::from("#")))
+                    }
+                }
+                (State::DecodingChar(name), _) => {
+                    // The first character is taken as is, so `#\(` and `#\ ` work.
+                    let in_name = name.chars().all(char::is_alphanumeric)
+                        && (name.is_empty() || is_symbol_constituent(ch));
+                    if in_name {
+                        (
+                            State::DecodingChar(name.to_string() + &ch.to_string()),
+                            Token::NoToken,
+                        )
+                    } else {
+                        self.chiter.unget(ch);
+                        (State::FinishedToken, char_token(name))
+                    }
+                }
+                (State::LineComment, _) => {
+                    if ch == '\n' {
+                        (State::Begin, Token::NoToken)
+                    } else {
+                        (State::LineComment, Token::NoToken)
+                    }
+                }
+                (State::BlockComment(depth), _) => {
+                    if ch == '|' {
+                        (State::BlockCommentBar(*depth), Token::NoToken)
+                    } else if ch == '#' {
+                        (State::BlockCommentHash(*depth), Token::NoToken)
+                    } else {
+                        (State::BlockComment(*depth), Token::NoToken)
+                    }
+                }
+                (State::BlockCommentBar(depth), _) => {
+                    if ch == '#' && *depth == 1 {
+                        (State::Begin, Token::NoToken)
+                    } else if ch == '#' {
+                        (State::BlockComment(depth - 1), Token::NoToken)
+                    } else if ch == '|' {
+                        (State::BlockCommentBar(*depth), Token::NoToken)
+                    } else {
+                        (State::BlockComment(*depth), Token::NoToken)
+                    }
+                }
+                (State::BlockCommentHash(depth), _) => {
+                    if ch == '|' {
+                        (State::BlockComment(depth + 1), Token::NoToken)
+                    } else if ch == '#' {
+                        (State::BlockCommentHash(*depth), Token::NoToken)
+                    } else {
+                        (State::BlockComment(*depth), Token::NoToken)
+                    }
+                }
                 (State::DecodingIdentifier, _)
+                | (State::DecodingKeyword, _)
                 | (State::DecodingInteger, _)
-                | (State::DecodingText, _) => panic!("Inconsistent state!"),
+                | (State::DecodingText, _)
+                | (State::TextEscape, _)
+                | (State::TextUnicodeOpen, _)
+                | (State::TextUnicode(_), _)
+                | (State::RawString(_), _)
+                | (State::RawStringClose(..), _) => panic!("Inconsistent state!"),
             },
         }
     }
 
+    /// Skips the next complete form, for `#_` datum comments.
+    fn skip_datum(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.token()? {
+                None => return Err(Error::Incomplete),
+                Some(Token::OpenList)
+                | Some(Token::OpenVector)
+                | Some(Token::OpenBracket)
+                | Some(Token::OpenBrace) => depth += 1,
+                Some(Token::CloseList) | Some(Token::CloseBracket) | Some(Token::CloseBrace)
+                    if depth == 0 =>
+                {
+                    return Err(Error::Syntax(String::from("missing form after #_")))
+                }
+                Some(Token::CloseList) | Some(Token::CloseBracket) | Some(Token::CloseBrace) => {
+                    depth -= 1
+                }
+                Some(Token::Quote) | Some(Token::Quasiquote) | Some(Token::Unquote) => continue,
+                Some(_) => (),
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
     pub fn token(&mut self) -> Result<Option<Token>> {
         if let Some(tk) = self.cache.take() {
             return Ok(Some(tk));
@@ -202,11 +552,34 @@ where
 
         loop {
             let ch = self.chiter.next().transpose()?;
+            if ch.is_none() {
+                match &self.state {
+                    (State::DecodingChar(name), _) if name.is_empty() => {
+                        return Err(Error::Incomplete)
+                    }
+                    (State::DecodingText, _)
+                    | (State::TextEscape, _)
+                    | (State::TextUnicodeOpen, _)
+                    | (State::TextUnicode(_), _)
+                    | (State::RawStringOpen(_), _)
+                    | (State::RawString(_), _)
+                    | (State::BlockComment(_), _)
+                    | (State::BlockCommentBar(_), _)
+                    | (State::BlockCommentHash(_), _)
+                    | (State::RawStringClose(..), _)
+                    | (State::DatumComment, _) => return Err(Error::Incomplete),
+                    _ => (),
+                }
+            }
             self.state_machine(ch);
             match &self.state {
+                (State::DatumComment, _) => {
+                    self.state = (State::Begin, Token::NoToken);
+                    self.skip_datum()?;
+                }
                 (State::FinishedToken, Token::NoToken) => return Ok(None),
                 (State::FinishedToken, token) => return Ok(Some(token.clone())),
-                (_,_) => continue,
+                (_, _) => continue,
             }
         }
     }
@@ -257,4 +630,163 @@ mod tests {
 
         assert_eq!(cmp, tokenized);
     }
+
+    #[test]
+    fn get_operator_tokens() {
+        use Token::*;
+        let input = "(<= -1 x) (/= a b)(copy-list l)";
+        let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+
+        let mut tokenized = Vec::<Token>::new();
+        while let Some(token) = tokens.token().unwrap() {
+            tokenized.push(token);
+        }
+
+        let cmp: Vec<Token> = vec![
+            OpenList,
+            Identifier(String::from("<=")),
+            Identifier(String::from("-1")),
+            Identifier(String::from("x")),
+            CloseList,
+            OpenList,
+            Identifier(String::from("/=")),
+            Identifier(String::from("a")),
+            Identifier(String::from("b")),
+            CloseList,
+            OpenList,
+            Identifier(String::from("copy-list")),
+            Identifier(String::from("l")),
+            CloseList,
+        ];
+
+        assert_eq!(cmp, tokenized);
+    }
+
+    #[test]
+    fn skip_comments() {
+        use Token::*;
+        let input = "; line comment (a b)
+            (x #| block #| nested |# (c) |# y ; trailing
+             #_ (skipped (form)) #_ 'z #_#_ 1 2 w)";
+        let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+
+        let mut tokenized = Vec::<Token>::new();
+        while let Some(token) = tokens.token().unwrap() {
+            tokenized.push(token);
+        }
+
+        let cmp: Vec<Token> = vec![
+            OpenList,
+            Identifier(String::from("x")),
+            Identifier(String::from("y")),
+            Identifier(String::from("w")),
+            CloseList,
+        ];
+
+        assert_eq!(cmp, tokenized);
+    }
+
+    #[test]
+    fn unterminated_comments() {
+        for input in &["#| never closed", "#| a #| b |#", "(a #_"] {
+            let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+            let mut result = tokens.token();
+            while let Ok(Some(_)) = result {
+                result = tokens.token();
+            }
+            assert!(matches!(result, Err(Error::Incomplete)), "{}", input);
+        }
+    }
+
+    #[test]
+    fn string_escapes() {
+        let input = r###""say \"hi\"\n\t\\ \r\0 \u{1F600}" #r"C:\dir" #r#"a "quoted" word"# #r##"x"#y"##"###;
+        let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+
+        let mut tokenized = Vec::<Token>::new();
+        while let Some(token) = tokens.token().unwrap() {
+            tokenized.push(token);
+        }
+
+        let cmp: Vec<Token> = vec![
+            Token::Text(String::from("say \"hi\"\n\t\\ \r\0 \u{1F600}")),
+            Token::Text(String::from("C:\\dir")),
+            Token::Text(String::from("a \"quoted\" word")),
+            Token::Text(String::from("x\"#y")),
+        ];
+
+        assert_eq!(cmp, tokenized);
+
+        for input in &[r#""\q""#, r#""\u{110000}""#, r#""\u41""#] {
+            let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+            assert!(
+                matches!(tokens.token(), Ok(Some(Token::Invalid(_)))),
+                "{}",
+                input
+            );
+        }
+        for input in &[r#""abc\"#, r#""\u{41"#, r##"#r#"abc"##] {
+            let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+            assert!(
+                matches!(tokens.token(), Err(Error::Incomplete)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn char_literals() {
+        let input = r"(#\a #\A #\( #\  #\space #\Newline #\x41 #\λ)";
+        let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+
+        let mut tokenized = Vec::<Token>::new();
+        while let Some(token) = tokens.token().unwrap() {
+            tokenized.push(token);
+        }
+
+        let cmp: Vec<Token> = vec![
+            Token::OpenList,
+            Token::Char('a'),
+            Token::Char('A'),
+            Token::Char('('),
+            Token::Char(' '),
+            Token::Char(' '),
+            Token::Char('\n'),
+            Token::Char('A'),
+            Token::Char('λ'),
+            Token::CloseList,
+        ];
+
+        assert_eq!(cmp, tokenized);
+
+        let mut tokens = Tokenizer::new(Cursor::new(r"#\bogus").bytes());
+        assert!(matches!(tokens.token(), Ok(Some(Token::Invalid(_)))));
+        let mut tokens = Tokenizer::new(Cursor::new(r"#\").bytes());
+        assert!(matches!(tokens.token(), Err(Error::Incomplete)));
+    }
+
+    #[test]
+    fn keywords() {
+        use Token::*;
+        let input = "(:a :key-word) : x :b";
+        let mut tokens = Tokenizer::new(Cursor::new(input).bytes());
+
+        let mut tokenized = Vec::<Token>::new();
+        while let Some(token) = tokens.token().unwrap() {
+            tokenized.push(token);
+        }
+
+        let cmp: Vec<Token> = vec![
+            OpenList,
+            Keyword(String::from("a")),
+            Keyword(String::from("key-word")),
+            CloseList,
+            Identifier(String::from(":")),
+            Identifier(String::from("x")),
+            Keyword(String::from("b")),
+        ];
+
+        assert_eq!(cmp, tokenized);
+    }
 }
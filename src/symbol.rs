@@ -0,0 +1,149 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// An interned symbol name. Interning the same name twice gives the same
+/// `Symbol`, so comparing and hashing symbols is comparing integers.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// Index of the name, or of the prefix of a gensym, in the interner.
+    id: u32,
+    /// Counter of a gensym, zero for interned symbols.
+    generation: u32,
+}
+
+/// Interned names are never freed: every name read, interned or converted
+/// with `STRING->SYMBOL` stays for the life of the process. Gensyms only
+/// store their prefix.
+struct Interner {
+    ids: HashMap<Arc<str>, u32>,
+    names: Vec<Arc<str>>,
+}
+
+impl Interner {
+    fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            names: Vec::new(),
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> u32 {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let name: Arc<str> = Arc::from(name);
+        let id = u32::try_from(self.names.len()).expect("too many symbols interned");
+        self.names.push(Arc::clone(&name));
+        self.ids.insert(name, id);
+        id
+    }
+}
+
+lazy_static! {
+    static ref INTERNER: RwLock<Interner> = RwLock::new(Interner::new());
+}
+
+static GENSYMS: AtomicU32 = AtomicU32::new(0);
+
+impl Symbol {
+    /// The symbol named exactly `name`.
+    pub fn intern(name: &str) -> Symbol {
+        let known = INTERNER.read().unwrap().ids.get(name).copied();
+        let id = known.unwrap_or_else(|| INTERNER.write().unwrap().intern(name));
+        Symbol { id, generation: 0 }
+    }
+
+    /// A fresh symbol named `prefix` and a counter, distinct from every other
+    /// symbol including those read or interned with the same name.
+    pub fn gensym(prefix: &str) -> Symbol {
+        let generation = GENSYMS
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| count.checked_add(1))
+            .expect("too many gensyms")
+            + 1;
+        Symbol {
+            id: Symbol::intern(prefix).id,
+            generation,
+        }
+    }
+
+    pub fn name(self) -> Arc<str> {
+        let name = Arc::clone(&INTERNER.read().unwrap().names[self.id as usize]);
+        match self.generation {
+            0 => name,
+            generation => Arc::from(format!("{}{}", name, generation)),
+        }
+    }
+}
+
+/// How identifiers become symbols, for the reader, the environment and host
+/// code naming Lustre symbols alike.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Identifiers are upper-cased, so `foo` and `FOO` are the same symbol and
+    /// builtins are named `CAR`.
+    #[default]
+    Upcase,
+    /// Identifiers are kept as written, so `foo` and `FOO` differ and builtins
+    /// are named `car`.
+    Preserve,
+}
+
+impl Case {
+    /// The symbol for the identifier `name`.
+    pub fn symbol(self, name: &str) -> Symbol {
+        match self {
+            Case::Upcase => Symbol::intern(&name.to_uppercase()),
+            Case::Preserve => Symbol::intern(name),
+        }
+    }
+
+    /// The symbol for a builtin named `name` in upper case.
+    pub fn builtin(self, name: &str) -> Symbol {
+        match self {
+            Case::Upcase => Symbol::intern(name),
+            Case::Preserve => Symbol::intern(&name.to_lowercase()),
+        }
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&INTERNER.read().unwrap().names[self.id as usize])?;
+        match self.generation {
+            0 => Ok(()),
+            generation => write!(f, "{}", generation),
+        }
+    }
+}
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Symbol({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interner_test() {
+        assert_eq!(Symbol::intern("κόσμε"), Symbol::intern("κόσμε"));
+        assert_eq!(Case::Upcase.symbol("κόσμε"), Symbol::intern("ΚΌΣΜΕ"));
+        assert_eq!(Case::Preserve.symbol("Foo"), Symbol::intern("Foo"));
+        assert_eq!(Case::Preserve.builtin("IF"), Symbol::intern("if"));
+        assert_ne!(Symbol::intern("a"), Symbol::intern("A"));
+
+        let generated = Symbol::gensym("G");
+        assert_ne!(generated, Symbol::gensym("G"));
+        assert_ne!(generated, Symbol::intern(&generated.name()));
+        assert!(generated.name().starts_with('G'));
+        assert_eq!(*generated.name(), generated.to_string());
+        // Gensyms share their prefix's entry instead of adding one each.
+        assert_eq!(Symbol::intern("G").id, generated.id);
+    }
+}